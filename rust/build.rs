@@ -2,14 +2,82 @@ use std::env;
 use std::path::PathBuf;
 use std::process::Command;
 
+// Cargo features this build script expects to be declared in `Cargo.toml`:
+//
+//   [features]
+//   default = ["vendored"]
+//   vendored = []   # build the bundled C library with CMake (current default)
+//   system = []     # probe for an installed 7z_ffi/lzma via pkg-config first
+//
+// With `system` enabled, a successful pkg-config probe skips the CMake build
+// entirely; if probing fails (not installed, no pkg-config binary) this
+// falls back to the vendored CMake build just like the default does.
+
 fn main() {
     // Get the manifest directory (rust/)
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     let manifest_path = PathBuf::from(&manifest_dir);
-    
+
     // Project root is parent of rust/
     let project_root = manifest_path.parent().unwrap();
-    
+
+    if cfg!(feature = "system") && try_system_library() {
+        println!("cargo:warning=Using system-installed 7z_ffi (found via pkg-config)");
+        return;
+    }
+
+    build_vendored(project_root);
+
+    // Re-run if C sources change
+    println!("cargo:rerun-if-changed=../src/");
+    println!("cargo:rerun-if-changed=../include/");
+    println!("cargo:rerun-if-changed=../CMakeLists.txt");
+    println!("cargo:rerun-if-changed=../build/");
+    println!("cargo:rerun-if-env-changed=TARGET");
+    println!("cargo:rerun-if-env-changed=HOST");
+
+    // Check for required system dependencies
+    check_system_dependencies();
+}
+
+/// Probe for an installed `7z_ffi` (and its `lzma` dependency) via
+/// `pkg-config`, emitting the link directives pkg-config reports if found.
+/// Returns `false` (without emitting anything) if either library isn't
+/// registered with pkg-config, so the caller can fall back to vendoring.
+fn try_system_library() -> bool {
+    let probe = |name: &str| -> bool {
+        Command::new("pkg-config")
+            .args(["--exists", name])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    };
+
+    if !probe("7z_ffi") || !probe("lzma") {
+        return false;
+    }
+
+    for name in ["7z_ffi", "lzma"] {
+        let output = Command::new("pkg-config").args(["--libs", "--cflags", name]).output();
+        match output {
+            Ok(output) if output.status.success() => {
+                let flags = String::from_utf8_lossy(&output.stdout);
+                for flag in flags.split_whitespace() {
+                    if let Some(dir) = flag.strip_prefix("-L") {
+                        println!("cargo:rustc-link-search=native={}", dir);
+                    } else if let Some(lib) = flag.strip_prefix("-l") {
+                        println!("cargo:rustc-link-lib={}", lib);
+                    }
+                }
+            }
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+fn build_vendored(project_root: &std::path::Path) {
     // Check if C library is already built
     let build_dir = project_root.join("build");
     let lib_path = if cfg!(target_os = "windows") {
@@ -18,17 +86,29 @@ fn main() {
         // On Unix-like systems, look for static library
         build_dir.join("lib7z_ffi.a")
     };
-    
+
     // Build C library if it doesn't exist
     if !lib_path.exists() {
         println!("cargo:warning=Building C library...");
-        
+
+        let target = env::var("TARGET").unwrap_or_default();
+        let host = env::var("HOST").unwrap_or_default();
+
+        let mut cmake_args = vec!["-B".to_string(), "build".to_string(), "-DCMAKE_BUILD_TYPE=Release".to_string()];
+        cmake_args.push("-DCMAKE_POSITION_INDEPENDENT_CODE=ON".to_string());
+        if !target.is_empty() {
+            cmake_args.push(format!("-DCC_TARGET={}", target));
+        }
+        if !host.is_empty() {
+            cmake_args.push(format!("-DCC_HOST={}", host));
+        }
+
         // Run cmake configuration
         let cmake_status = Command::new("cmake")
-            .args(&["-B", "build", "-DCMAKE_BUILD_TYPE=Release"])
+            .args(&cmake_args)
             .current_dir(project_root)
             .status();
-        
+
         match cmake_status {
             Ok(status) if status.success() => {
                 println!("cargo:warning=CMake configuration successful");
@@ -44,14 +124,14 @@ fn main() {
                 println!("cargo:warning=  cd .. && cmake -B build && cmake --build build");
             }
         }
-        
+
         // Run cmake build
         if build_dir.exists() {
             let build_status = Command::new("cmake")
                 .args(&["--build", "build", "--config", "Release"])
                 .current_dir(project_root)
                 .status();
-            
+
             match build_status {
                 Ok(status) if status.success() => {
                     println!("cargo:warning=C library build successful");
@@ -67,29 +147,29 @@ fn main() {
     } else {
         println!("cargo:warning=C library already built at: {}", lib_path.display());
     }
-    
+
     // Tell cargo where to find the library
     let lib_dir = if cfg!(target_os = "windows") {
         build_dir.join("Release")
     } else {
         build_dir.clone()
     };
-    
+
     if lib_dir.exists() && lib_path.exists() {
         println!("cargo:rustc-link-search=native={}", lib_dir.display());
         println!("cargo:rustc-link-lib=static=7z_ffi");
-        
+
         // Link system libraries (no OpenSSL needed - using pure Rust crypto)
         #[cfg(not(target_os = "windows"))]
         {
             // Link pthread for thread-safe error reporting
             #[cfg(target_os = "macos")]
             println!("cargo:rustc-link-lib=dylib=pthread");
-            
+
             #[cfg(target_os = "linux")]
             println!("cargo:rustc-link-lib=dylib=pthread");
         }
-        
+
         // On Windows, link against bcrypt for system crypto (if C library needs it)
         #[cfg(target_os = "windows")]
         {
@@ -99,19 +179,10 @@ fn main() {
         println!("cargo:warning=Library directory not found: {}", lib_dir.display());
         println!("cargo:warning=Please build the C library manually:");
         println!("cargo:warning=  cd .. && cmake -B build && cmake --build build");
-        
+
         // Still try to link, might be in a custom location
         println!("cargo:rustc-link-lib=static=7z_ffi");
     }
-    
-    // Re-run if C sources change
-    println!("cargo:rerun-if-changed=../src/");
-    println!("cargo:rerun-if-changed=../include/");
-    println!("cargo:rerun-if-changed=../CMakeLists.txt");
-    println!("cargo:rerun-if-changed=../build/");
-    
-    // Check for required system dependencies
-    check_system_dependencies();
 }
 
 fn check_system_dependencies() {
@@ -119,7 +190,7 @@ fn check_system_dependencies() {
     let cmake_check = Command::new("cmake")
         .arg("--version")
         .output();
-    
+
     match cmake_check {
         Ok(output) if output.status.success() => {
             let version = String::from_utf8_lossy(&output.stdout);
@@ -132,7 +203,7 @@ fn check_system_dependencies() {
             println!("cargo:warning=On Linux: sudo apt-get install cmake");
         }
     }
-    
+
     // Note: OpenSSL is no longer required - using pure Rust crypto (RustCrypto crates)
     println!("cargo:warning=Using pure Rust AES encryption (no OpenSSL required)");
 }
@@ -32,6 +32,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         CompressionLevel::Normal,
         500_000, // 500KB volumes
         None,
+        false,
     )?;
     
     // Check if split files were created
@@ -3,14 +3,18 @@
 //! This module provides advanced functionality for:
 //! - Split/multi-volume archives for easier transfer and storage
 //! - Raw LZMA/LZMA2 compression for .lzma and .xz files
+//! - lzip (.lz) container read/write, including multi-member streams
 //! - Detailed error reporting with context and suggestions
 
 use crate::error::{Error, Result};
 use crate::ffi;
 use crate::CompressionLevel;
 use std::ffi::{CString, CStr};
-use std::path::Path;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::os::raw::c_char;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 /// Detailed error information with context and actionable suggestions
 #[derive(Debug, Clone)]
@@ -123,6 +127,9 @@ pub fn get_version() -> String {
 ///                   - 734_003_200 (700MB CD)
 ///                   - 4_700_372_992 (4.7GB DVD)
 ///                   - 4_294_967_296 (4GB FAT32 limit)
+/// * `password` - Optional password; required if `encrypt_headers` is set
+/// * `encrypt_headers` - Also encrypt the header (filenames, sizes, layout),
+///                       equivalent to 7-Zip's `-mhe=on`. Requires `password`.
 ///
 /// # Example
 ///
@@ -137,6 +144,7 @@ pub fn get_version() -> String {
 ///     CompressionLevel::Normal,
 ///     4_294_967_296, // 4GB
 ///     None,
+///     false,
 /// )?;
 /// // Creates: large_backup.7z.001, large_backup.7z.002, ...
 /// # Ok::<(), seven_zip::Error>(())
@@ -147,11 +155,18 @@ pub fn create_split_archive(
     level: CompressionLevel,
     volume_size: u64,
     password: Option<&str>,
+    encrypt_headers: bool,
 ) -> Result<()> {
+    if encrypt_headers && password.is_none() {
+        return Err(Error::InvalidParameter(
+            "encrypt_headers requires a password".to_string(),
+        ));
+    }
+
     let archive_path = archive_path.as_ref().to_str()
         .ok_or_else(|| Error::Io("Invalid path encoding".to_string()))?;
     let c_archive = CString::new(archive_path)?;
-    
+
     // Convert input paths to C strings
     let c_paths: Result<Vec<CString>> = input_paths
         .iter()
@@ -162,24 +177,32 @@ pub fn create_split_archive(
         })
         .collect();
     let c_paths = c_paths?;
-    
+
     // Create null-terminated array of pointers
     let mut c_path_ptrs: Vec<*const c_char> = c_paths
         .iter()
         .map(|s| s.as_ptr())
         .collect();
     c_path_ptrs.push(std::ptr::null());
-    
+
     // Setup compression options
     let c_password = password
         .map(|p| CString::new(p))
         .transpose()?;
-    
+
     let c_options = ffi::SevenZipCompressOptions {
         num_threads: 0, // auto
         dict_size: 0,   // auto
         solid: 1,       // solid archive
         password: c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+        method: 0, // LZMA2
+        ppmd_order: 0,
+        ppmd_mem_mb: 0,
+        bzip2_block_size: 0,
+        encrypt_headers: encrypt_headers as i32,
+        store_symlinks: 1,
+        preserve_permissions: 1,
+        preserve_timestamps: 1,
     };
     
     unsafe {
@@ -253,6 +276,168 @@ pub fn extract_split_archive(
     Ok(())
 }
 
+// ============================================================================
+// Benchmarking
+// ============================================================================
+
+/// Measured outcome of one [`algotest`] configuration
+#[derive(Debug, Clone)]
+pub struct AlgotestResult {
+    /// `split_size` used for this run (bytes, 0 = single volume)
+    pub split_size: u64,
+    /// `num_threads` used for this run (0 = let the SDK choose)
+    pub num_threads: u32,
+    /// Compression level used for this run
+    pub level: CompressionLevel,
+    /// Wall-clock time to create the archive
+    pub elapsed: std::time::Duration,
+    /// `sample_input`'s size divided by `elapsed`, in MB/s
+    pub throughput_mb_per_sec: f64,
+    /// Combined size of every volume the archive was split into
+    pub total_size: u64,
+    /// Percentage of `sample_input`'s size saved by compression
+    pub percent_saved: f64,
+    /// Size of each individual volume (a single entry if `split_size` is 0)
+    pub volume_sizes: Vec<u64>,
+    /// Mean of `volume_sizes`
+    pub volume_size_mean: f64,
+    /// Population standard deviation of `volume_sizes`
+    pub volume_size_stddev: f64,
+}
+
+/// Sweep combinations of split size, thread count, and compression level
+/// against `sample_input`, reporting throughput, resulting size, and
+/// per-volume breakdown for each
+///
+/// Each combination of `split_sizes`, `thread_counts`, and `levels` creates
+/// a throwaway multi-volume archive from `sample_input` via
+/// [`crate::archive::SevenZip::create_archive_streaming`], times it, and
+/// records the result before deleting the volumes it produced. Lets callers
+/// pick parameters (e.g. the 50MB/4-thread defaults the multi-volume example
+/// hardcodes) empirically for their own hardware and data instead of
+/// guessing. Results are returned in the order their configurations were
+/// given; pass them to [`format_algotest_table`] for a printable comparison.
+pub fn algotest(
+    sample_input: impl AsRef<Path>,
+    split_sizes: &[u64],
+    thread_counts: &[u32],
+    levels: &[CompressionLevel],
+) -> Result<Vec<AlgotestResult>> {
+    let sample_input = sample_input.as_ref();
+    let input_size = std::fs::metadata(sample_input)?.len();
+    let sz = crate::archive::SevenZip::new()?;
+    let mut results = Vec::new();
+
+    for &split_size in split_sizes {
+        for &num_threads in thread_counts {
+            for &level in levels {
+                let archive_path = unique_temp_path(".7z");
+                let options = crate::archive::StreamOptions {
+                    split_size,
+                    num_threads,
+                    ..Default::default()
+                };
+
+                let start = std::time::Instant::now();
+                let outcome =
+                    sz.create_archive_streaming(&archive_path, &[sample_input], level, Some(&options), None);
+                let elapsed = start.elapsed();
+
+                let volume_sizes = volume_sizes(&archive_path, split_size);
+                let sizes: Vec<u64> = volume_sizes
+                    .iter()
+                    .filter_map(|p| std::fs::metadata(p).ok().map(|m| m.len()))
+                    .collect();
+                for volume in &volume_sizes {
+                    let _ = std::fs::remove_file(volume);
+                }
+                outcome?;
+
+                let total_size: u64 = sizes.iter().sum();
+                let mean = total_size as f64 / sizes.len().max(1) as f64;
+                let variance = sizes
+                    .iter()
+                    .map(|&s| {
+                        let diff = s as f64 - mean;
+                        diff * diff
+                    })
+                    .sum::<f64>()
+                    / sizes.len().max(1) as f64;
+
+                results.push(AlgotestResult {
+                    split_size,
+                    num_threads,
+                    level,
+                    elapsed,
+                    throughput_mb_per_sec: (input_size as f64 / 1_048_576.0) / elapsed.as_secs_f64().max(f64::EPSILON),
+                    total_size,
+                    percent_saved: if input_size > 0 {
+                        100.0 * (1.0 - total_size as f64 / input_size as f64)
+                    } else {
+                        0.0
+                    },
+                    volume_sizes: sizes,
+                    volume_size_mean: mean,
+                    volume_size_stddev: variance.sqrt(),
+                });
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Paths of the volumes a [`algotest`] (or
+/// [`create_split_archive`]-produced) archive at `base` was written to
+///
+/// Multi-volume output is named `{base}.001`, `{base}.002`, ...; a
+/// `split_size` of 0 means the single unsplit `base` path itself.
+///
+/// `pub(crate)` so [`crate::archive::SevenZip::create_archive_streaming`]
+/// can reuse the same volume-discovery logic to emit its post-archiving
+/// `VolumeStarted`/`VolumeFinished` progress events.
+pub(crate) fn volume_sizes(base: &Path, split_size: u64) -> Vec<PathBuf> {
+    if split_size == 0 {
+        return vec![base.to_path_buf()];
+    }
+    let mut volumes = Vec::new();
+    let mut n = 1u32;
+    loop {
+        let candidate = PathBuf::from(format!("{}.{:03}", base.display(), n));
+        if !candidate.exists() {
+            break;
+        }
+        volumes.push(candidate);
+        n += 1;
+    }
+    if volumes.is_empty() {
+        volumes.push(base.to_path_buf());
+    }
+    volumes
+}
+
+/// Render [`algotest`] results as a plain-text comparison table: split size,
+/// thread count, level, speed, total size, percent saved, and volume size
+/// mean ± standard deviation, one row per configuration
+pub fn format_algotest_table(results: &[AlgotestResult]) -> String {
+    let mut out = String::new();
+    out.push_str("split_size    threads  level      MB/s     total_size   saved%   vol_mean±stddev\n");
+    for r in results {
+        out.push_str(&format!(
+            "{:<13} {:<8} {:<10} {:<8.2} {:<12} {:<8.1} {:.0}±{:.0}\n",
+            r.split_size,
+            r.num_threads,
+            format!("{:?}", r.level),
+            r.throughput_mb_per_sec,
+            r.total_size,
+            r.percent_saved,
+            r.volume_size_mean,
+            r.volume_size_stddev,
+        ));
+    }
+    out
+}
+
 // ============================================================================
 // Raw LZMA/LZMA2 Compression
 // ============================================================================
@@ -419,14 +604,969 @@ pub fn decompress_lzma2(
         
         if result != ffi::SevenZipErrorCode::SEVENZIP_OK { return Err(Error::from_code(result)); }
     }
-    
+
+    Ok(())
+}
+
+// ============================================================================
+// Parallel LZMA2/XZ compression
+// ============================================================================
+//
+// `compress_lzma2` (above) asks the C library for one single-block `.xz`
+// stream per call. `compress_lzma2_parallel` splits the input into
+// independent chunks, compresses each one (still one `compress_lzma2` call
+// per chunk, so still the existing single-threaded codec underneath) across
+// a small thread pool, then stitches the resulting single-block streams
+// into one valid multi-block `.xz` stream: the first chunk's Stream Header
+// is kept, each chunk's Block (read back out of its own Index, so no
+// hand-parsing of LZMA2 filter properties is needed) is concatenated, and
+// one combined Index/Footer covering every block is built fresh. A standard
+// `xz`/`liblzma` reader decodes the result exactly like one produced by
+// `xz --block-size`/`pixz`.
+
+/// Write an xz variable-length integer (7 bits per byte, little-endian,
+/// high bit set on every byte but the last)
+fn write_vli(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            out.push(byte | 0x80);
+        } else {
+            out.push(byte);
+            break;
+        }
+    }
+}
+
+/// Read an xz variable-length integer starting at `*pos`, advancing it past
+/// the bytes consumed
+fn read_vli(data: &[u8], pos: &mut usize) -> Result<u64> {
+    let mut value: u64 = 0;
+    let mut shift = 0u32;
+    loop {
+        let byte = *data.get(*pos).ok_or_else(|| {
+            Error::InvalidArchive("truncated xz variable-length integer".to_string())
+        })?;
+        *pos += 1;
+        value |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+        if shift > 63 {
+            return Err(Error::InvalidArchive("xz variable-length integer too long".to_string()));
+        }
+    }
+}
+
+/// One chunk's Block, pulled out of its own single-block xz stream, ready
+/// to be concatenated into a combined multi-block stream
+struct XzBlockRecord {
+    /// Block Header + compressed data + Check + Block Padding, verbatim
+    region: Vec<u8>,
+    /// This record's "Unpadded Size" field, as the original encoder wrote it
+    unpadded_size: u64,
+    /// This chunk's uncompressed length
+    uncompressed_size: u64,
+}
+
+/// Split a single-block xz stream (as produced by [`compress_lzma2`]) into
+/// its 12-byte Stream Header and its [`XzBlockRecord`], by reading the
+/// Index the encoder already wrote rather than parsing the Block Header's
+/// LZMA2 filter properties directly
+fn extract_single_block_region(xz: &[u8]) -> Result<(Vec<u8>, XzBlockRecord)> {
+    const MAGIC: &[u8; 6] = b"\xFD7zXZ\x00";
+    if xz.len() < 24 || &xz[0..6] != MAGIC {
+        return Err(Error::InvalidArchive("not a valid xz stream".to_string()));
+    }
+    let footer = &xz[xz.len() - 12..];
+    if &footer[10..12] != b"YZ" {
+        return Err(Error::InvalidArchive("missing xz stream footer magic".to_string()));
+    }
+    let backward_size_field = u32::from_le_bytes(footer[4..8].try_into().unwrap());
+    let index_size = (backward_size_field as usize + 1) * 4;
+    if xz.len() < 12 + index_size + 12 {
+        return Err(Error::InvalidArchive("xz index size exceeds stream length".to_string()));
+    }
+
+    let index_start = xz.len() - 12 - index_size;
+    let block_region = xz[12..index_start].to_vec();
+
+    let index = &xz[index_start..index_start + index_size];
+    if index[0] != 0x00 {
+        return Err(Error::InvalidArchive("unexpected xz index indicator byte".to_string()));
+    }
+    let mut pos = 1usize;
+    let num_records = read_vli(index, &mut pos)?;
+    if num_records != 1 {
+        return Err(Error::NotImplemented(
+            "compress_lzma2_parallel can only merge single-block per-chunk xz streams".to_string(),
+        ));
+    }
+    let unpadded_size = read_vli(index, &mut pos)?;
+    let uncompressed_size = read_vli(index, &mut pos)?;
+
+    Ok((
+        xz[0..12].to_vec(),
+        XzBlockRecord { region: block_region, unpadded_size, uncompressed_size },
+    ))
+}
+
+fn compress_chunks_parallel(
+    chunks: &[&[u8]],
+    level: CompressionLevel,
+    threads: usize,
+) -> Result<Vec<Vec<u8>>> {
+    if chunks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let slots: Vec<Mutex<Option<Result<Vec<u8>>>>> =
+        (0..chunks.len()).map(|_| Mutex::new(None)).collect();
+    let next_index = AtomicUsize::new(0);
+    let worker_count = threads.max(1).min(chunks.len());
+
+    std::thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                let i = next_index.fetch_add(1, Ordering::Relaxed);
+                if i >= chunks.len() {
+                    break;
+                }
+                *slots[i].lock().unwrap() = Some(compress_lzma2_bytes(chunks[i], level));
+            });
+        }
+    });
+
+    slots
+        .into_iter()
+        .map(|m| m.into_inner().unwrap().expect("every chunk index is claimed by exactly one worker"))
+        .collect()
+}
+
+/// Compress a file to `.xz` format using multiple threads, by splitting the
+/// input into `block_size`-byte chunks compressed independently and
+/// concatenated into one multi-block xz stream
+///
+/// Mirrors how `plzip` parallelizes `lzip` and `pixz`/`xz --block-size`
+/// parallelize `xz`: splitting loses a little compression ratio at each
+/// chunk boundary (LZMA2's dictionary can't look across blocks), in
+/// exchange for scaling compression time down with `threads`.
+/// Decompression of the result needs no special handling - any xz reader,
+/// including [`decompress_lzma2`], already decodes multi-block streams.
+///
+/// # Example
+///
+/// ```no_run
+/// use seven_zip::advanced;
+/// use seven_zip::CompressionLevel;
+///
+/// advanced::compress_lzma2_parallel(
+///     "large_file.bin",
+///     "large_file.bin.xz",
+///     CompressionLevel::Normal,
+///     8,
+///     16 * 1024 * 1024,
+/// )?;
+/// # Ok::<(), seven_zip::Error>(())
+/// ```
+pub fn compress_lzma2_parallel(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    level: CompressionLevel,
+    threads: usize,
+    block_size: u64,
+) -> Result<()> {
+    if block_size == 0 {
+        return Err(Error::InvalidParameter("block_size must be nonzero".to_string()));
+    }
+
+    let data = std::fs::read(input_path.as_ref())?;
+    let chunks: Vec<&[u8]> = data.chunks(block_size as usize).collect();
+    let compressed_chunks = compress_chunks_parallel(&chunks, level, threads)?;
+
+    let mut stream_header: Option<Vec<u8>> = None;
+    let mut records = Vec::with_capacity(compressed_chunks.len());
+    let mut body = Vec::new();
+    for xz in &compressed_chunks {
+        let (header, record) = extract_single_block_region(xz)?;
+        match &stream_header {
+            None => stream_header = Some(header),
+            Some(existing) if *existing != header => {
+                return Err(Error::NotImplemented(
+                    "all chunks must share the same xz stream flags/check type to merge".to_string(),
+                ));
+            }
+            Some(_) => {}
+        }
+        body.extend_from_slice(&record.region);
+        records.push(record);
+    }
+    let stream_header = match stream_header {
+        Some(h) => h,
+        None => extract_single_block_region(&compress_lzma2_bytes(&[], level)?)?.0,
+    };
+
+    let mut index = vec![0x00u8];
+    write_vli(&mut index, records.len() as u64);
+    for record in &records {
+        write_vli(&mut index, record.unpadded_size);
+        write_vli(&mut index, record.uncompressed_size);
+    }
+    while index.len() % 4 != 0 {
+        index.push(0);
+    }
+    let index_crc = crate::archive::crc32_ieee(&index);
+
+    let index_size_with_crc = index.len() + 4;
+    let backward_size_field = (index_size_with_crc / 4 - 1) as u32;
+    let stream_flags = &stream_header[6..8];
+    let mut footer_fields = Vec::with_capacity(6);
+    footer_fields.extend_from_slice(&backward_size_field.to_le_bytes());
+    footer_fields.extend_from_slice(stream_flags);
+    let footer_crc = crate::archive::crc32_ieee(&footer_fields);
+
+    let mut out = Vec::with_capacity(stream_header.len() + body.len() + index.len() + 16);
+    out.extend_from_slice(&stream_header);
+    out.extend_from_slice(&body);
+    out.extend_from_slice(&index);
+    out.extend_from_slice(&index_crc.to_le_bytes());
+    out.extend_from_slice(&footer_crc.to_le_bytes());
+    out.extend_from_slice(&footer_fields);
+    out.extend_from_slice(b"YZ");
+
+    std::fs::write(output_path.as_ref(), &out)?;
     Ok(())
 }
 
+// ============================================================================
+// lzip (.lz) container
+// ============================================================================
+//
+// lzip wraps the same raw LZMA1 stream this module's `compress_lzma`
+// already produces, just in its own framing (a 6-byte header and a 20-byte
+// CRC32/size trailer per member) with fixed properties (lc=3, lp=0, pb=2).
+// So rather than a second codec, the functions below reuse
+// `compress_lzma_bytes`/`decompress_lzma_bytes` as the underlying LZMA1
+// engine and only translate between its classic 13-byte `.lzma` header and
+// lzip's own - `Error::NotImplemented` is returned if the underlying
+// encoder ever produces a properties byte other than lzip's fixed one.
+
+const LZIP_MAGIC: &[u8; 4] = b"LZIP";
+const LZIP_VERSION: u8 = 1;
+const LZIP_HEADER_SIZE: usize = 6;
+const LZIP_TRAILER_SIZE: usize = 20;
+/// lzip fixes lc=3, lp=0, pb=2; encoded the same way the classic `.lzma`
+/// properties byte is: `(pb * 5 + lp) * 9 + lc`
+const LZIP_STANDARD_PROPS: u8 = 0x5D;
+
+fn lzip_encode_dict_size(dict_size: u64) -> u8 {
+    let mut n = 12u32;
+    while (1u64 << n) < dict_size && n < 29 {
+        n += 1;
+    }
+    n as u8
+}
+
+fn lzip_decode_dict_size(byte: u8) -> u64 {
+    let base = 1u64 << (byte & 0x1F);
+    let fraction = ((byte >> 5) & 0x07) as u64;
+    base - (base / 16) * fraction
+}
+
+fn encode_lzip_member(data: &[u8], level: CompressionLevel) -> Result<Vec<u8>> {
+    let classic = compress_lzma_bytes(data, level)?;
+    if classic.len() < 13 {
+        return Err(Error::Compress(
+            "LZMA encoder produced a header shorter than the classic 13 bytes".to_string(),
+        ));
+    }
+    let props = classic[0];
+    if props != LZIP_STANDARD_PROPS {
+        return Err(Error::NotImplemented(format!(
+            "lzip requires LZMA properties byte {:#04x} (lc=3, lp=0, pb=2); the encoder used {:#04x}",
+            LZIP_STANDARD_PROPS, props
+        )));
+    }
+    let dict_size = u32::from_le_bytes(classic[1..5].try_into().unwrap()) as u64;
+    let raw_stream = &classic[13..];
+
+    let mut member = Vec::with_capacity(LZIP_HEADER_SIZE + raw_stream.len() + LZIP_TRAILER_SIZE);
+    member.extend_from_slice(LZIP_MAGIC);
+    member.push(LZIP_VERSION);
+    member.push(lzip_encode_dict_size(dict_size));
+    member.extend_from_slice(raw_stream);
+    member.extend_from_slice(&crate::archive::crc32_ieee(data).to_le_bytes());
+    member.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    let total_size = (LZIP_HEADER_SIZE + raw_stream.len() + LZIP_TRAILER_SIZE) as u64;
+    member.extend_from_slice(&total_size.to_le_bytes());
+
+    Ok(member)
+}
+
+fn decode_lzip_member(member: &[u8]) -> Result<Vec<u8>> {
+    if member.len() < LZIP_HEADER_SIZE + LZIP_TRAILER_SIZE {
+        return Err(Error::InvalidArchive("lzip member shorter than header + trailer".to_string()));
+    }
+    if &member[0..4] != LZIP_MAGIC {
+        return Err(Error::InvalidArchive("missing 'LZIP' magic".to_string()));
+    }
+    let version = member[4];
+    if version != LZIP_VERSION {
+        return Err(Error::NotImplemented(format!("unsupported lzip member version {}", version)));
+    }
+    let dict_size = lzip_decode_dict_size(member[5]);
+
+    let trailer = &member[member.len() - LZIP_TRAILER_SIZE..];
+    let expected_crc = u32::from_le_bytes(trailer[0..4].try_into().unwrap());
+    let uncompressed_size = u64::from_le_bytes(trailer[4..12].try_into().unwrap());
+    let total_size = u64::from_le_bytes(trailer[12..20].try_into().unwrap());
+    if total_size != member.len() as u64 {
+        return Err(Error::InvalidArchive(format!(
+            "lzip member trailer declares total size {} but member is {} bytes",
+            total_size,
+            member.len()
+        )));
+    }
+
+    let raw_stream = &member[LZIP_HEADER_SIZE..member.len() - LZIP_TRAILER_SIZE];
+
+    // Rebuild the classic `.lzma` container this crate's decoder
+    // understands (1 properties byte + 4-byte little-endian dictionary
+    // size + 8-byte little-endian uncompressed size) around the same raw
+    // stream - lzip's fixed properties match this crate's encoder, so the
+    // compressed bytes themselves need no translation.
+    let mut classic = Vec::with_capacity(13 + raw_stream.len());
+    classic.push(LZIP_STANDARD_PROPS);
+    classic.extend_from_slice(&(dict_size as u32).to_le_bytes());
+    classic.extend_from_slice(&uncompressed_size.to_le_bytes());
+    classic.extend_from_slice(raw_stream);
+
+    let decoded = decompress_lzma_bytes(&classic)?;
+    if decoded.len() as u64 != uncompressed_size {
+        return Err(Error::InvalidArchive(format!(
+            "lzip member decoded to {} bytes but trailer declares {}",
+            decoded.len(),
+            uncompressed_size
+        )));
+    }
+    let actual_crc = crate::archive::crc32_ieee(&decoded);
+    if actual_crc != expected_crc {
+        return Err(Error::ChecksumMismatch {
+            name: "<lzip member>".to_string(),
+            expected: expected_crc,
+            actual: actual_crc,
+        });
+    }
+    Ok(decoded)
+}
+
+/// Find the end of the lzip member starting at `data[start..]`, by looking
+/// for the next member's `LZIP` magic and checking whether that split
+/// point's trailer declares a matching total size
+///
+/// If no later occurrence of the magic produces a consistent trailer, this
+/// is the last member: it ends at the first position whose trailer declares
+/// a matching total size (so bytes appended after it are left over), or at
+/// `data.len()` if no position does.
+fn find_member_end(data: &[u8], start: usize) -> usize {
+    let mut search_pos = start + LZIP_HEADER_SIZE;
+    while search_pos < data.len() {
+        match data[search_pos..].windows(LZIP_MAGIC.len()).position(|w| w == LZIP_MAGIC) {
+            Some(p) => {
+                let candidate_end = search_pos + p;
+                let candidate = &data[start..candidate_end];
+                if candidate.len() >= LZIP_HEADER_SIZE + LZIP_TRAILER_SIZE {
+                    let total = u64::from_le_bytes(
+                        candidate[candidate.len() - 8..].try_into().unwrap(),
+                    );
+                    if total == candidate.len() as u64 {
+                        return candidate_end;
+                    }
+                }
+                search_pos = candidate_end + 1;
+            }
+            None => break,
+        }
+    }
+    (start + LZIP_HEADER_SIZE + LZIP_TRAILER_SIZE..=data.len())
+        .find(|&end| {
+            u64::from_le_bytes(data[end - 8..end].try_into().unwrap()) == (end - start) as u64
+        })
+        .unwrap_or(data.len())
+}
+
+fn decompress_lzip_bytes(data: &[u8]) -> Result<Vec<u8>> {
+    let mut offset = 0usize;
+    let mut output = Vec::new();
+    while offset < data.len() {
+        let end = find_member_end(data, offset);
+        output.extend_from_slice(&decode_lzip_member(&data[offset..end])?);
+        offset = end;
+    }
+    Ok(output)
+}
+
+/// Compress a file to lzip format (`.lz`)
+///
+/// Creates a single-member lzip stream: the `"LZIP"` magic, version, and
+/// coded dictionary size, the raw LZMA1 data, then a trailer with the
+/// uncompressed data's CRC32, its size, and the member's total size.
+/// Compatible with the `lzip`/`plzip`/`lzlib` toolchain.
+///
+/// # Example
+///
+/// ```no_run
+/// use seven_zip::advanced;
+/// use seven_zip::CompressionLevel;
+///
+/// advanced::compress_lzip(
+///     "data.bin",
+///     "data.bin.lz",
+///     CompressionLevel::Normal,
+/// )?;
+/// # Ok::<(), seven_zip::Error>(())
+/// ```
+pub fn compress_lzip(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+    level: CompressionLevel,
+) -> Result<()> {
+    let data = std::fs::read(input_path.as_ref())?;
+    let member = encode_lzip_member(&data, level)?;
+    std::fs::write(output_path.as_ref(), &member)?;
+    Ok(())
+}
+
+/// Decompress a lzip file (`.lz`)
+///
+/// Verifies the trailing CRC32 and uncompressed size of every member, and
+/// transparently decodes concatenated multi-member streams (as produced by
+/// `cat a.lz b.lz > both.lz`) in sequence until the input is exhausted.
+///
+/// # Example
+///
+/// ```no_run
+/// use seven_zip::advanced;
+///
+/// advanced::decompress_lzip(
+///     "data.bin.lz",
+///     "data.bin",
+/// )?;
+/// # Ok::<(), seven_zip::Error>(())
+/// ```
+pub fn decompress_lzip(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+) -> Result<()> {
+    let data = std::fs::read(input_path.as_ref())?;
+    let decoded = decompress_lzip_bytes(&data)?;
+    std::fs::write(output_path.as_ref(), &decoded)?;
+    Ok(())
+}
+
+// ============================================================================
+// In-memory / stream-based LZMA compression
+// ============================================================================
+//
+// The C library only exposes path-based entry points, so the buffer- and
+// `Read`/`Write`-based helpers below stage data through short-lived temp
+// files. This keeps one chunked-copy-loop implementation instead of a
+// second FFI surface, at the cost of a filesystem round-trip; callers that
+// need to avoid disk I/O entirely should wait for a true streaming FFI shim.
+
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn unique_temp_path(suffix: &str) -> PathBuf {
+    let n = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("seven_zip_{}_{}{}", std::process::id(), n, suffix))
+}
+
+/// Compress an in-memory buffer to raw LZMA format
+///
+/// Equivalent to [`compress_lzma`], but operates on a byte slice instead of
+/// a file path, for data produced in memory (e.g. a database dump or
+/// network payload).
+pub fn compress_lzma_bytes(data: &[u8], level: CompressionLevel) -> Result<Vec<u8>> {
+    let input_path = unique_temp_path(".in");
+    let output_path = unique_temp_path(".lzma");
+    std::fs::write(&input_path, data)?;
+
+    let result = compress_lzma(&input_path, &output_path, level);
+    let _ = std::fs::remove_file(&input_path);
+    result?;
+
+    let compressed = std::fs::read(&output_path)?;
+    let _ = std::fs::remove_file(&output_path);
+    Ok(compressed)
+}
+
+/// Decompress an in-memory raw LZMA buffer
+///
+/// Equivalent to [`decompress_lzma`], but operates on a byte slice instead
+/// of a file path.
+pub fn decompress_lzma_bytes(data: &[u8]) -> Result<Vec<u8>> {
+    let input_path = unique_temp_path(".lzma");
+    let output_path = unique_temp_path(".out");
+    std::fs::write(&input_path, data)?;
+
+    let result = decompress_lzma(&input_path, &output_path);
+    let _ = std::fs::remove_file(&input_path);
+    result?;
+
+    let decompressed = std::fs::read(&output_path)?;
+    let _ = std::fs::remove_file(&output_path);
+    Ok(decompressed)
+}
+
+/// Compress an in-memory buffer to LZMA2/xz format
+///
+/// Equivalent to [`compress_lzma2`], but operates on a byte slice instead of
+/// a file path; also used internally by [`compress_lzma2_parallel`] to
+/// compress each chunk.
+pub fn compress_lzma2_bytes(data: &[u8], level: CompressionLevel) -> Result<Vec<u8>> {
+    let input_path = unique_temp_path(".in");
+    let output_path = unique_temp_path(".xz");
+    std::fs::write(&input_path, data)?;
+
+    let result = compress_lzma2(&input_path, &output_path, level);
+    let _ = std::fs::remove_file(&input_path);
+    result?;
+
+    let compressed = std::fs::read(&output_path)?;
+    let _ = std::fs::remove_file(&output_path);
+    Ok(compressed)
+}
+
+/// Decompress an in-memory LZMA2/xz buffer
+///
+/// Equivalent to [`decompress_lzma2`], but operates on a byte slice instead
+/// of a file path; used by [`test_archive`] to check a `.xz` stream without
+/// writing the decoded output anywhere.
+pub fn decompress_lzma2_bytes(data: &[u8]) -> Result<Vec<u8>> {
+    let input_path = unique_temp_path(".xz");
+    let output_path = unique_temp_path(".out");
+    std::fs::write(&input_path, data)?;
+
+    let result = decompress_lzma2(&input_path, &output_path);
+    let _ = std::fs::remove_file(&input_path);
+    result?;
+
+    let decompressed = std::fs::read(&output_path)?;
+    let _ = std::fs::remove_file(&output_path);
+    Ok(decompressed)
+}
+
+/// Compress an arbitrary `Read` source to raw LZMA, writing the result to `writer`
+///
+/// Convenient for a caller that only has a `Read`/`Write` pair rather than
+/// file paths (e.g. stdin → stdout), but not a streaming codec: the C
+/// library's raw LZMA functions only take file paths (see
+/// [`compress_lzma`]), so this reads `reader` fully into memory, shells out
+/// through [`compress_lzma_bytes`], and writes the result in one shot — the
+/// whole input and the whole compressed output each exist as an in-memory
+/// buffer for the duration of the call.
+pub fn compress_to_writer(
+    mut reader: impl Read,
+    mut writer: impl Write,
+    level: CompressionLevel,
+) -> Result<()> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let compressed = compress_lzma_bytes(&data, level)?;
+    writer.write_all(&compressed)?;
+    Ok(())
+}
+
+/// Decompress a raw LZMA stream from `reader`, writing the result to `writer`
+///
+/// Shares [`compress_to_writer`]'s tradeoff: `reader` is read fully into
+/// memory before [`decompress_lzma_bytes`] runs, so both the compressed
+/// input and the decompressed output are fully buffered, not streamed.
+pub fn extract_to_writer(mut reader: impl Read, mut writer: impl Write) -> Result<()> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let decompressed = decompress_lzma_bytes(&data)?;
+    writer.write_all(&decompressed)?;
+    Ok(())
+}
+
+// ============================================================================
+// Damaged-archive recovery
+// ============================================================================
+//
+// `repair_archive`/`merge_copies` use the existing whole-buffer
+// `decompress_lzma_bytes` round-trip as their sole integrity oracle (this
+// module has no lower-level block/CRC introspection into raw LZMA, unlike
+// `.xz`'s per-block check or `.lz`'s per-member one) - raw LZMA stores no
+// CRC of its own, so "the C decoder accepts the whole stream" is the only
+// integrity check available.
+
+/// Outcome of a successful [`repair_archive`] call
+#[derive(Debug, Clone)]
+pub struct RepairReport {
+    /// Byte offset within the compressed stream that was flipped to make it
+    /// decode, or `None` if the input already decoded unmodified
+    pub corrected_offset: Option<usize>,
+    /// Byte value originally at `corrected_offset`
+    pub original_byte: u8,
+    /// Byte value written at `corrected_offset` after the fix
+    pub repaired_byte: u8,
+}
+
+/// Attempt to repair a raw-LZMA (`.lzma`) file damaged by a single flipped
+/// bit, lziprecover-style
+///
+/// Tries the input unmodified first, then every single-bit flip of every
+/// byte in the compressed stream (in offset order), re-running
+/// [`decompress_lzma_bytes`] after each flip until one decodes cleanly. On
+/// success the corrected compressed bytes (not the decompressed content)
+/// are written to `output_path` and the flipped offset is reported;
+/// [`Error::Extract`] is returned if no single-bit flip decodes.
+///
+/// This is `O(8 * input.len())` decode attempts, so it only scales to
+/// small-to-medium streams, and only recovers single-bit damage - multiple
+/// flipped bits, or bytes dropped/inserted, are out of scope. There is no
+/// way to narrow the search further: unlike `.xz`, raw LZMA carries no
+/// internal block checksums to localize the damage before decoding.
+pub fn repair_archive(
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+) -> Result<RepairReport> {
+    let data = std::fs::read(input_path.as_ref())?;
+
+    if decompress_lzma_bytes(&data).is_ok() {
+        std::fs::write(output_path.as_ref(), &data)?;
+        return Ok(RepairReport { corrected_offset: None, original_byte: 0, repaired_byte: 0 });
+    }
+
+    for offset in 0..data.len() {
+        for bit in 0..8u8 {
+            let mut candidate = data.clone();
+            candidate[offset] ^= 1 << bit;
+            if decompress_lzma_bytes(&candidate).is_ok() {
+                let repaired_byte = candidate[offset];
+                std::fs::write(output_path.as_ref(), &candidate)?;
+                return Ok(RepairReport {
+                    corrected_offset: Some(offset),
+                    original_byte: data[offset],
+                    repaired_byte,
+                });
+            }
+        }
+    }
+
+    Err(Error::Extract(format!(
+        "no single-bit flip of the {} compressed bytes produced a valid LZMA stream",
+        data.len()
+    )))
+}
+
+/// Outcome of a successful [`merge_copies`] call
+#[derive(Debug, Clone)]
+pub struct MergeReport {
+    /// Block size `merge_copies` was called with
+    pub block_size: usize,
+    /// The source copy's index (into the `copies` slice passed to
+    /// `merge_copies`) each block of the repaired output was drawn from
+    pub sources: Vec<usize>,
+}
+
+/// Reconstruct one good archive out of several partially-corrupted copies,
+/// lziprecover's `--merge`/`--reproduce` mode
+///
+/// Splits every copy into `block_size`-byte blocks and compares them:
+/// blocks every copy agrees on are assumed good and taken from `copies[0]`
+/// unmodified, while blocks where the copies disagree are the candidates
+/// for corruption. Every combination of which copy each disagreeing block
+/// is drawn from is tried (any block every copy agrees on never needs to be
+/// guessed) against [`decompress_lzma_bytes`] until one combination decodes
+/// cleanly; that combination is written to `output_path` and reported.
+///
+/// Never writes a block that wasn't chosen because the *whole* reconstructed
+/// stream validated, and returns [`Error::Extract`] rather than a partial
+/// file if no combination does. Since whole-stream validation is the only
+/// integrity signal raw LZMA offers (see the module-level note above),
+/// checking every combination is unavoidable; with more than a handful of
+/// disagreeing blocks this is exponential in their count, so a combination
+/// count over one million returns [`Error::InvalidParameter`] up front
+/// rather than attempting it - that many simultaneously-disputed blocks
+/// means the copies are too different to reconstruct this way.
+pub fn merge_copies(
+    copies: &[impl AsRef<Path>],
+    output_path: impl AsRef<Path>,
+    block_size: usize,
+) -> Result<MergeReport> {
+    if copies.len() < 2 {
+        return Err(Error::InvalidParameter(
+            "merge_copies needs at least two copies to merge".to_string(),
+        ));
+    }
+    if block_size == 0 {
+        return Err(Error::InvalidParameter("block_size must be nonzero".to_string()));
+    }
+
+    let buffers: Result<Vec<Vec<u8>>> = copies.iter().map(|p| Ok(std::fs::read(p.as_ref())?)).collect();
+    let buffers = buffers?;
+    let len = buffers[0].len();
+    if buffers.iter().any(|b| b.len() != len) {
+        return Err(Error::InvalidParameter(
+            "all copies must be the same length to merge by block".to_string(),
+        ));
+    }
+
+    let num_blocks = len.div_ceil(block_size.max(1));
+    let mut sources = vec![0usize; num_blocks];
+    let mut disagreeing = Vec::new();
+    for block_idx in 0..num_blocks {
+        let start = block_idx * block_size;
+        let end = (start + block_size).min(len);
+        if buffers.iter().any(|b| b[start..end] != buffers[0][start..end]) {
+            disagreeing.push(block_idx);
+        }
+    }
+
+    const MAX_COMBINATIONS: u64 = 1_000_000;
+    let combinations = (copies.len() as u64).saturating_pow(disagreeing.len() as u32);
+    if combinations > MAX_COMBINATIONS {
+        return Err(Error::InvalidParameter(format!(
+            "{} disagreeing blocks across {} copies would require checking {} combinations; \
+             the copies are too different to reconstruct by block merging",
+            disagreeing.len(),
+            copies.len(),
+            combinations
+        )));
+    }
+
+    let mut merged = buffers[0].clone();
+    let mut choice = vec![0usize; disagreeing.len()];
+    loop {
+        for (i, &block_idx) in disagreeing.iter().enumerate() {
+            let start = block_idx * block_size;
+            let end = (start + block_size).min(len);
+            merged[start..end].copy_from_slice(&buffers[choice[i]][start..end]);
+        }
+
+        if decompress_lzma_bytes(&merged).is_ok() {
+            for (i, &block_idx) in disagreeing.iter().enumerate() {
+                sources[block_idx] = choice[i];
+            }
+            std::fs::write(output_path.as_ref(), &merged)?;
+            return Ok(MergeReport { block_size, sources });
+        }
+
+        let mut i = 0;
+        loop {
+            if i == choice.len() {
+                return Err(Error::Extract(format!(
+                    "no combination of the {} supplied copies across {} disagreeing blocks decodes to a valid stream",
+                    copies.len(),
+                    disagreeing.len()
+                )));
+            }
+            choice[i] += 1;
+            if choice[i] < copies.len() {
+                break;
+            }
+            choice[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+// ============================================================================
+// Integrity testing for single-file streams
+// ============================================================================
+//
+// `SevenZip::test_integrity` (archive.rs) covers `.7z`'s central directory;
+// the single-file formats this module handles (`.lzma`, `.xz`, `.lz`) have
+// no such directory of their own, so `test_archive` below decodes the whole
+// stream and reports each member's pass/fail outcome directly, the way
+// `7zz t`/`xz -t`/`lzip -t` would.
+
+/// Number of leading bytes of unrecognized trailing data captured in
+/// [`StreamTestReport::trailing_data`]
+const TRAILING_DATA_PREVIEW: usize = 16;
+
+/// One member's outcome from [`test_archive`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberReport {
+    /// 0-based position of this member within the stream. Always `0` for
+    /// `.lzma`/`.xz`, which this crate only ever reads as a single member;
+    /// `.lz` (lzip) streams can concatenate several.
+    pub index: usize,
+    /// Decoded size in bytes, once decoding and checksum verification
+    /// succeed
+    pub uncompressed_size: Option<u64>,
+    /// `None` if this member decoded and passed its checksum; `Some(msg)`
+    /// describing the first failure otherwise
+    pub error: Option<String>,
+}
+
+/// Outcome of [`test_archive`]: one [`MemberReport`] per member, plus any
+/// unrecognized bytes found appended after the stream's logical end
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamTestReport {
+    /// Per-member pass/fail results, in stream order
+    pub members: Vec<MemberReport>,
+    /// First [`TRAILING_DATA_PREVIEW`] bytes of data found after the
+    /// stream's last recognized member, if any
+    ///
+    /// A common real-world corruption: a retried download or transfer that
+    /// appended instead of overwriting, or a concatenated multi-part file
+    /// that wasn't trimmed. The stream itself may still be perfectly
+    /// readable, so this is reported as a warning alongside an otherwise
+    /// clean [`StreamTestReport`] rather than failing the whole call.
+    pub trailing_data: Option<Vec<u8>>,
+}
+
+impl StreamTestReport {
+    /// `true` if every member decoded and passed its checksum
+    ///
+    /// Does not consider [`StreamTestReport::trailing_data`] - trailing
+    /// garbage is a warning about bytes outside the stream, not a defect in
+    /// the stream itself.
+    pub fn all_ok(&self) -> bool {
+        self.members.iter().all(|m| m.error.is_none())
+    }
+}
+
+/// Verify a `.lzma`, `.xz`, or `.lz` (lzip) stream without writing any
+/// decoded output to disk, returning a per-member pass/fail report
+///
+/// Dispatches on `archive_path`'s extension, case-insensitively: `.xz` is
+/// read as a single LZMA2/xz stream, `.lz` as one or more concatenated
+/// lzip members, and anything else (including `.lzma`) as a classic
+/// lzma_alone stream.
+///
+/// Trailing garbage appended after the stream's logical end is reported via
+/// [`StreamTestReport::trailing_data`] instead of failing the call - see
+/// that field's docs. This only applies to `.xz` and `.lz`, which both have
+/// a well-defined end (an xz Stream Footer, a lzip member trailer); classic
+/// `.lzma` has neither, so this crate's FFI-based decoder is simply handed
+/// every byte and either decodes it or doesn't, with no way to tell "the
+/// whole file is one valid stream" apart from "a valid stream followed by
+/// unrelated bytes".
+pub fn test_archive(archive_path: impl AsRef<Path>) -> Result<StreamTestReport> {
+    let data = std::fs::read(archive_path.as_ref())?;
+    let extension = archive_path
+        .as_ref()
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("xz") => test_xz_bytes(&data),
+        Some("lz") => test_lzip_bytes(&data),
+        _ => test_lzma_bytes(&data),
+    }
+}
+
+fn test_lzma_bytes(data: &[u8]) -> Result<StreamTestReport> {
+    let (error, uncompressed_size) = match decompress_lzma_bytes(data) {
+        Ok(decoded) => (None, Some(decoded.len() as u64)),
+        Err(e) => (Some(e.to_string()), None),
+    };
+    Ok(StreamTestReport {
+        members: vec![MemberReport { index: 0, uncompressed_size, error }],
+        trailing_data: None,
+    })
+}
+
+fn test_xz_bytes(data: &[u8]) -> Result<StreamTestReport> {
+    let stream_end = find_xz_stream_end(data)?;
+
+    let (error, uncompressed_size) = match decompress_lzma2_bytes(&data[..stream_end]) {
+        Ok(decoded) => (None, Some(decoded.len() as u64)),
+        Err(e) => (Some(e.to_string()), None),
+    };
+
+    let trailing_data = trailing_preview(data, stream_end);
+
+    Ok(StreamTestReport {
+        members: vec![MemberReport { index: 0, uncompressed_size, error }],
+        trailing_data,
+    })
+}
+
+fn test_lzip_bytes(data: &[u8]) -> Result<StreamTestReport> {
+    let mut members = Vec::new();
+    let mut offset = 0usize;
+    let mut index = 0usize;
+
+    while offset < data.len() {
+        if !data[offset..].starts_with(LZIP_MAGIC) {
+            break;
+        }
+        let end = find_member_end(data, offset);
+        let (error, uncompressed_size) = match decode_lzip_member(&data[offset..end]) {
+            Ok(decoded) => (None, Some(decoded.len() as u64)),
+            Err(e) => (Some(e.to_string()), None),
+        };
+        members.push(MemberReport { index, uncompressed_size, error });
+        offset = end;
+        index += 1;
+    }
+
+    if members.is_empty() {
+        return Err(Error::InvalidArchive("missing 'LZIP' magic".to_string()));
+    }
+
+    Ok(StreamTestReport { members, trailing_data: trailing_preview(data, offset) })
+}
+
+/// Locate the end of an xz stream by finding the rightmost Stream Footer
+/// whose Backward Size arithmetic is internally consistent
+///
+/// A standalone xz file's Footer is simply its last 12 bytes, but that
+/// assumption breaks the moment unrelated bytes are appended after it; this
+/// instead scans backward for `"YZ"` footer-magic occurrences and accepts
+/// the first (i.e. rightmost) one whose Index arithmetic checks out,
+/// treating everything after it as trailing data.
+fn find_xz_stream_end(data: &[u8]) -> Result<usize> {
+    const MAGIC: &[u8; 6] = b"\xFD7zXZ\x00";
+    if data.len() < 24 || &data[0..6] != MAGIC {
+        return Err(Error::InvalidArchive("not a valid xz stream".to_string()));
+    }
+
+    let mut search_end = data.len();
+    while search_end >= 12 {
+        let candidate_end = match data[..search_end].windows(2).rposition(|w| w == b"YZ") {
+            Some(p) => p + 2,
+            None => break,
+        };
+        if candidate_end >= 12 {
+            let footer = &data[candidate_end - 12..candidate_end];
+            let backward_size_field = u32::from_le_bytes(footer[4..8].try_into().unwrap());
+            let index_size = (backward_size_field as usize + 1) * 4;
+            if candidate_end >= 12 + index_size + 12 {
+                let index_start = candidate_end - 12 - index_size;
+                if index_start >= 12 && data.get(index_start) == Some(&0x00) {
+                    return Ok(candidate_end);
+                }
+            }
+        }
+        search_end = candidate_end.saturating_sub(2);
+    }
+
+    Err(Error::InvalidArchive("missing xz stream footer magic".to_string()))
+}
+
+/// Capture up to [`TRAILING_DATA_PREVIEW`] bytes of whatever follows
+/// `data[..stream_end]`, or `None` if `stream_end` already reaches the end
+fn trailing_preview(data: &[u8], stream_end: usize) -> Option<Vec<u8>> {
+    if stream_end >= data.len() {
+        return None;
+    }
+    let preview_end = (stream_end + TRAILING_DATA_PREVIEW).min(data.len());
+    Some(data[stream_end..preview_end].to_vec())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_get_version() {
         let version = get_version();
@@ -441,4 +1581,244 @@ mod tests {
         let msg = get_error_string(5);
         assert!(!msg.is_empty());
     }
+
+    #[test]
+    fn test_unique_temp_path_is_unique() {
+        let a = unique_temp_path(".in");
+        let b = unique_temp_path(".in");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_volume_sizes_no_split_returns_base_path() {
+        let base = PathBuf::from("/tmp/seven_zip_algotest_example.7z");
+        assert_eq!(volume_sizes(&base, 0), vec![base]);
+    }
+
+    #[test]
+    fn test_vli_roundtrips() {
+        for value in [0u64, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut buf = Vec::new();
+            write_vli(&mut buf, value);
+            let mut pos = 0;
+            assert_eq!(read_vli(&buf, &mut pos).unwrap(), value);
+            assert_eq!(pos, buf.len());
+        }
+    }
+
+    #[test]
+    fn test_read_vli_rejects_truncated_input() {
+        let mut pos = 0;
+        assert!(read_vli(&[0x80], &mut pos).is_err());
+    }
+
+    #[test]
+    fn test_extract_single_block_region_rejects_bad_magic() {
+        let bogus = vec![0u8; 32];
+        assert!(matches!(extract_single_block_region(&bogus), Err(Error::InvalidArchive(_))));
+    }
+
+    #[test]
+    fn test_compress_lzma2_parallel_rejects_zero_block_size() {
+        let result = compress_lzma2_parallel("in.bin", "out.xz", CompressionLevel::Normal, 4, 0);
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_compress_lzma2_parallel_roundtrips_across_multiple_blocks() {
+        let data = b"the quick brown fox jumps over the lazy dog ".repeat(200);
+        let input = unique_temp_path(".in");
+        let compressed = unique_temp_path(".xz");
+        let output = unique_temp_path(".out");
+        std::fs::write(&input, &data).unwrap();
+
+        let result = compress_lzma2_parallel(&input, &compressed, CompressionLevel::Normal, 4, 1024)
+            .and_then(|_| decompress_lzma2(&compressed, &output));
+
+        let roundtrip_ok = result.is_ok() && std::fs::read(&output).map(|d| d == data).unwrap_or(false);
+
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&compressed);
+        let _ = std::fs::remove_file(&output);
+
+        assert!(roundtrip_ok || result.is_err());
+    }
+
+    #[test]
+    fn test_lzip_dict_size_roundtrips_without_fraction() {
+        for n in 12..=29u32 {
+            let size = 1u64 << n;
+            assert_eq!(lzip_decode_dict_size(lzip_encode_dict_size(size)), size);
+        }
+    }
+
+    #[test]
+    fn test_lzip_encode_dict_size_clamps_to_supported_range() {
+        assert_eq!(lzip_encode_dict_size(1), 12);
+        assert_eq!(lzip_encode_dict_size(u64::MAX), 29);
+    }
+
+    #[test]
+    fn test_decode_lzip_member_rejects_missing_magic() {
+        let member = vec![0u8; LZIP_HEADER_SIZE + LZIP_TRAILER_SIZE];
+        assert!(matches!(decode_lzip_member(&member), Err(Error::InvalidArchive(_))));
+    }
+
+    #[test]
+    fn test_decode_lzip_member_rejects_mismatched_total_size() {
+        let mut member = Vec::new();
+        member.extend_from_slice(LZIP_MAGIC);
+        member.push(LZIP_VERSION);
+        member.push(lzip_encode_dict_size(1 << 20));
+        member.extend_from_slice(&[0u8; 4]); // crc32
+        member.extend_from_slice(&0u64.to_le_bytes()); // uncompressed size
+        member.extend_from_slice(&999u64.to_le_bytes()); // wrong total size
+        assert!(matches!(decode_lzip_member(&member), Err(Error::InvalidArchive(_))));
+    }
+
+    #[test]
+    fn test_lzip_roundtrip_through_compress_and_decompress() {
+        let data = b"the quick brown fox jumps over the lazy dog".repeat(4);
+        let input = unique_temp_path(".in");
+        let compressed = unique_temp_path(".lz");
+        let output = unique_temp_path(".out");
+        std::fs::write(&input, &data).unwrap();
+
+        let result = compress_lzip(&input, &compressed, CompressionLevel::Normal)
+            .and_then(|_| decompress_lzip(&compressed, &output));
+
+        let roundtrip_ok = result.is_ok() && std::fs::read(&output).map(|d| d == data).unwrap_or(false);
+
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&compressed);
+        let _ = std::fs::remove_file(&output);
+
+        // Only meaningful when the C library is actually linked; otherwise
+        // just confirm the call fails cleanly rather than panicking.
+        assert!(roundtrip_ok || result.is_err());
+    }
+
+    #[test]
+    fn test_find_xz_stream_end_rejects_missing_magic() {
+        let bogus = vec![0u8; 32];
+        assert!(matches!(find_xz_stream_end(&bogus), Err(Error::InvalidArchive(_))));
+    }
+
+    #[test]
+    fn test_trailing_preview_returns_none_at_exact_end() {
+        let data = vec![1u8, 2, 3];
+        assert_eq!(trailing_preview(&data, 3), None);
+    }
+
+    #[test]
+    fn test_trailing_preview_truncates_to_preview_len() {
+        let data = vec![0u8; TRAILING_DATA_PREVIEW + 10];
+        let preview = trailing_preview(&data, 0).unwrap();
+        assert_eq!(preview.len(), TRAILING_DATA_PREVIEW);
+    }
+
+    #[test]
+    fn test_test_lzip_bytes_rejects_missing_magic() {
+        let bogus = vec![0u8; 32];
+        assert!(matches!(test_lzip_bytes(&bogus), Err(Error::InvalidArchive(_))));
+    }
+
+    #[test]
+    fn test_test_archive_reports_trailing_data_after_lzip_member() {
+        let data = b"the quick brown fox jumps over the lazy dog".repeat(4);
+        let input = unique_temp_path(".in");
+        let compressed = unique_temp_path(".lz");
+        std::fs::write(&input, &data).unwrap();
+
+        let Ok(()) = compress_lzip(&input, &compressed, CompressionLevel::Normal) else {
+            let _ = std::fs::remove_file(&input);
+            return;
+        };
+
+        let mut with_junk = std::fs::read(&compressed).unwrap();
+        with_junk.extend_from_slice(b"trailing garbage");
+        std::fs::write(&compressed, &with_junk).unwrap();
+
+        let report = test_archive(&compressed).unwrap();
+
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&compressed);
+
+        assert!(report.all_ok());
+        assert_eq!(report.trailing_data.as_deref(), Some(&b"trailing garbage"[..]));
+    }
+
+    #[test]
+    fn test_test_archive_dispatches_on_extension_for_classic_lzma() {
+        let bogus = unique_temp_path(".lzma");
+        std::fs::write(&bogus, [0u8; 8]).unwrap();
+        let report = test_archive(&bogus);
+        let _ = std::fs::remove_file(&bogus);
+        assert!(report.is_ok());
+        assert!(!report.unwrap().all_ok());
+    }
+
+    #[test]
+    fn test_merge_copies_rejects_single_copy() {
+        let result = merge_copies(&["a.lzma"], "out.lzma", 16);
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_merge_copies_rejects_zero_block_size() {
+        let result = merge_copies(&["a.lzma", "b.lzma"], "out.lzma", 0);
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_merge_copies_rejects_mismatched_lengths() {
+        let a = unique_temp_path(".a");
+        let b = unique_temp_path(".b");
+        std::fs::write(&a, b"short").unwrap();
+        std::fs::write(&b, b"a bit longer").unwrap();
+
+        let result = merge_copies(&[&a, &b], unique_temp_path(".out"), 4);
+
+        let _ = std::fs::remove_file(&a);
+        let _ = std::fs::remove_file(&b);
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_repair_archive_passes_through_already_valid_input() {
+        let data = compress_lzma_bytes(b"the quick brown fox", CompressionLevel::Normal);
+        let Ok(data) = data else { return };
+
+        let input = unique_temp_path(".lzma");
+        let output = unique_temp_path(".repaired.lzma");
+        std::fs::write(&input, &data).unwrap();
+
+        let report = repair_archive(&input, &output);
+
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&output);
+
+        let Ok(report) = report else { return };
+        assert!(report.corrected_offset.is_none());
+    }
+
+    #[test]
+    fn test_format_algotest_table_has_header_and_row() {
+        let results = vec![AlgotestResult {
+            split_size: 50_000_000,
+            num_threads: 4,
+            level: CompressionLevel::Normal,
+            elapsed: std::time::Duration::from_secs(2),
+            throughput_mb_per_sec: 12.5,
+            total_size: 1_000_000,
+            percent_saved: 25.0,
+            volume_sizes: vec![500_000, 500_000],
+            volume_size_mean: 500_000.0,
+            volume_size_stddev: 0.0,
+        }];
+        let table = format_algotest_table(&results);
+        assert!(table.starts_with("split_size"));
+        assert!(table.contains("50000000"));
+        assert!(table.contains("Normal"));
+    }
 }
@@ -0,0 +1,2456 @@
+//! High-level archive operations
+//!
+//! This module provides the main [`SevenZip`] entry point for creating,
+//! listing, extracting and testing `.7z` archives.
+
+use crate::dedup;
+use crate::error::{Error, Result};
+use crate::ffi;
+use std::ffi::{CStr, CString};
+use std::io::{Cursor, Read, Write};
+use std::os::raw::{c_char, c_void};
+use std::path::{Path, PathBuf};
+
+/// Compression level for standard 7z archive creation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionLevel {
+    /// No compression, just store the data
+    Store,
+    /// Fastest compression, lower ratio
+    Fast,
+    /// Balanced speed/ratio (default)
+    Normal,
+    /// Slower compression, higher ratio
+    Maximum,
+    /// Slowest compression, best ratio
+    Ultra,
+}
+
+impl From<CompressionLevel> for i32 {
+    fn from(level: CompressionLevel) -> i32 {
+        match level {
+            CompressionLevel::Store => 0,
+            CompressionLevel::Fast => 1,
+            CompressionLevel::Normal => 5,
+            CompressionLevel::Maximum => 7,
+            CompressionLevel::Ultra => 9,
+        }
+    }
+}
+
+/// Compression codec used to pack archive entries
+///
+/// `Lzma2` is the default and the only codec with first-class 7z support;
+/// the others trade ratio/speed for compatibility with archives produced by
+/// tools that favor those codecs (e.g. `.zip` files created with `bzip2` or
+/// `ppmd`, or uncompressed `Copy` storage for already-compressed data).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMethod {
+    /// LZMA2 (default, best general-purpose ratio)
+    Lzma2,
+    /// LZMA (predecessor to LZMA2, slightly better ratio on small solid blocks)
+    Lzma,
+    /// BZip2 (slower, sometimes better on highly redundant text)
+    Bzip2,
+    /// PPMd (strong ratio on text, slow)
+    Ppmd,
+    /// Deflate (fast, ZIP-compatible, lower ratio)
+    Deflate,
+    /// No compression, just store the data
+    Copy,
+}
+
+impl Default for CompressionMethod {
+    fn default() -> Self {
+        CompressionMethod::Lzma2
+    }
+}
+
+/// Codec for [`SevenZip::compress_file`]/[`SevenZip::decompress_file`]'s raw
+/// single-stream (non-`.7z`) format
+///
+/// Only the codecs this SDK's native library actually implements (raw LZMA
+/// and the LZMA2/XZ-style container); a foreign codec like Zstd or Brotli
+/// isn't in its closed coder set and can't be added here without a new
+/// native encoder to back it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamCodec {
+    /// Raw LZMA stream (`.lzma`)
+    Lzma,
+    /// LZMA2 in an XZ-style container (`.xz`)
+    Lzma2,
+}
+
+impl Default for StreamCodec {
+    fn default() -> Self {
+        StreamCodec::Lzma2
+    }
+}
+
+impl From<CompressionMethod> for i32 {
+    fn from(method: CompressionMethod) -> i32 {
+        match method {
+            CompressionMethod::Lzma2 => 0,
+            CompressionMethod::Lzma => 1,
+            CompressionMethod::Bzip2 => 2,
+            CompressionMethod::Ppmd => 3,
+            CompressionMethod::Deflate => 4,
+            CompressionMethod::Copy => 5,
+        }
+    }
+}
+
+impl CompressionMethod {
+    /// Inverse of the `i32` conversion used for [`ArchiveEntry::method`];
+    /// `None` for coder IDs this SDK doesn't model (e.g. a real 7-Zip coder
+    /// this crate never writes itself, like AES or a delta filter).
+    fn from_i32(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(CompressionMethod::Lzma2),
+            1 => Some(CompressionMethod::Lzma),
+            2 => Some(CompressionMethod::Bzip2),
+            3 => Some(CompressionMethod::Ppmd),
+            4 => Some(CompressionMethod::Deflate),
+            5 => Some(CompressionMethod::Copy),
+            _ => None,
+        }
+    }
+}
+
+/// Tuning parameters for the PPMd codec
+///
+/// Left at `Default` (order 6, 16MB) the SDK auto-selects based on input size;
+/// set explicitly to trade memory for ratio on large text corpora.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PpmdOptions {
+    /// Model order, 2-32 (higher orders capture more context but cost more memory)
+    pub order: u32,
+    /// Model memory budget in megabytes
+    pub mem_mb: u32,
+}
+
+impl Default for PpmdOptions {
+    fn default() -> Self {
+        Self { order: 6, mem_mb: 16 }
+    }
+}
+
+/// Tuning parameters for the BZip2 codec
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bzip2Options {
+    /// Block size in units of 100KB, 1-9 (higher gives better ratio, more memory)
+    pub block_size: u32,
+}
+
+impl Default for Bzip2Options {
+    fn default() -> Self {
+        Self { block_size: 9 }
+    }
+}
+
+/// Tuning parameters for the LZMA2 codec
+///
+/// Left at `Default` (0) the SDK picks a dictionary size from the
+/// compression level; set explicitly to trade memory for ratio, or to cap
+/// memory use on constrained hosts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lzma2Options {
+    /// Dictionary size in bytes (0 = let the SDK choose)
+    pub dict_size: u32,
+}
+
+impl Default for Lzma2Options {
+    fn default() -> Self {
+        Self { dict_size: 0 }
+    }
+}
+
+/// Filesystem metadata handling for archive creation and extraction
+///
+/// All three flags default to `true`: the archiver tries to faithfully
+/// round-trip symlinks, Unix permission bits and timestamps, matching what
+/// a forensic or backup restore needs. Platforms without symlink support
+/// (e.g. Windows without developer mode) silently fall back to following
+/// the link and archiving its target instead of failing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetadataOptions {
+    /// Archive the symlink itself (target path) rather than following it
+    pub store_symlinks: bool,
+    /// Record and restore Unix permission bits (mode)
+    pub preserve_permissions: bool,
+    /// Record and restore modification/access timestamps
+    pub preserve_timestamps: bool,
+}
+
+impl Default for MetadataOptions {
+    fn default() -> Self {
+        Self {
+            store_symlinks: true,
+            preserve_permissions: true,
+            preserve_timestamps: true,
+        }
+    }
+}
+
+/// Safety budget for [`SevenZip::extract_secure`]
+///
+/// Left at `Default`, every limit is disabled (0 = unlimited) and no path
+/// rewriting happens, matching [`SevenZip::extract`]'s behavior; set these
+/// to cap how much a malicious or corrupted archive can make an extraction
+/// write, or to reshape where entries land.
+#[derive(Debug, Clone)]
+pub struct ExtractOptions {
+    /// Maximum total uncompressed bytes across all entries, checked against
+    /// the archive's listing up front and re-checked against the running
+    /// total as each entry is decoded. 0 disables the check.
+    pub max_total_size: u64,
+    /// Maximum number of entries the archive may contain. 0 disables the check.
+    pub max_entries: usize,
+    /// Drop this many leading path segments from every entry before
+    /// extracting it (e.g. `1` turns `wrapper/docs/readme.txt` into
+    /// `docs/readme.txt`), useful for archives that store everything under
+    /// one top-level wrapper folder. An entry with no more segments than this
+    /// is skipped rather than extracted to `output_dir` itself.
+    pub strip_components: usize,
+    /// Extract under this relative subdirectory of `output_dir` instead of
+    /// directly into it. Must itself be a plain relative path (no `..` or
+    /// absolute component); applied after `strip_components`.
+    pub dest_prefix: Option<PathBuf>,
+    /// Recompute each entry's CRC32 as it's decoded and compare it against
+    /// the value recorded in the archive header, failing the whole call
+    /// with [`Error::ChecksumMismatch`] on the first divergence rather than
+    /// writing a silently-corrupted file. Entries with no recorded CRC32
+    /// (see [`ArchiveEntry::crc32`]) are written unchecked. Disabled by
+    /// default, matching [`SevenZip::extract`]'s behavior.
+    pub verify_crc: bool,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            max_total_size: 0,
+            max_entries: 0,
+            strip_components: 0,
+            dest_prefix: None,
+            verify_crc: false,
+        }
+    }
+}
+
+/// Resolve `name` (an entry's stored archive path) against `output_dir`,
+/// rejecting any path that could escape it
+///
+/// Rejects root/prefix components (absolute paths, e.g. `/etc/passwd` or a
+/// Windows drive letter) and `..` parent components (Zip-Slip-style
+/// traversal, e.g. `../../etc/cron.d/x`) without ever calling `canonicalize`
+/// on a path that may not exist yet; `.` components are simply skipped.
+///
+/// `strip_components` leading `Normal` components of `name` are dropped
+/// before joining (returning `Ok(None)` if that consumes the whole path),
+/// and `dest_prefix`, if set, is joined onto `output_dir` first - both are
+/// resolved through the same component-by-component check as `name` itself,
+/// so neither can be used to smuggle the final path outside `output_dir`.
+fn sanitized_entry_path(
+    output_dir: &Path,
+    dest_prefix: Option<&Path>,
+    strip_components: usize,
+    name: &str,
+) -> Result<Option<PathBuf>> {
+    use std::path::Component;
+    let mut resolved = output_dir.to_path_buf();
+
+    if let Some(prefix) = dest_prefix {
+        for component in prefix.components() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => {}
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                    return Err(Error::UnsafeArchive(
+                        "dest_prefix must be a relative path with no '..' components".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut stripped = 0usize;
+    let mut pushed_any = false;
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => {
+                if stripped < strip_components {
+                    stripped += 1;
+                    continue;
+                }
+                resolved.push(part);
+                pushed_any = true;
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(Error::UnsafeArchive(format!(
+                    "entry '{}' has an unsafe path component and was not extracted",
+                    name
+                )));
+            }
+        }
+    }
+
+    if !pushed_any {
+        return Ok(None);
+    }
+    Ok(Some(resolved))
+}
+
+/// A single entry in an archive's listing
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    /// Entry path within the archive
+    pub name: String,
+    /// Uncompressed size in bytes
+    pub size: u64,
+    /// Compressed (packed) size in bytes
+    pub packed_size: u64,
+    /// Whether this entry is a directory
+    pub is_dir: bool,
+    /// CRC32 (IEEE) of the decompressed content, if recorded in the archive
+    /// header. Directory entries and some older archive variants omit it.
+    pub crc32: Option<u32>,
+    /// Coder used to pack this entry, if it maps to a [`CompressionMethod`]
+    /// this SDK recognizes. `None` for directories and for coders outside
+    /// this SDK's closed set (e.g. a foreign archive's AES or delta filter).
+    pub method: Option<CompressionMethod>,
+}
+
+impl ArchiveEntry {
+    /// Compression ratio as a percentage of space saved (0-100)
+    pub fn compression_ratio(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        (1.0 - (self.packed_size as f64 / self.size as f64)) * 100.0
+    }
+}
+
+/// How a single entry was handled by [`SevenZip::update_archive`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateAction {
+    /// The entry was new or changed and was recompressed
+    Recompressed,
+    /// The entry was unchanged and its packed stream was copied verbatim
+    /// from the existing archive, without recompression
+    Copied,
+}
+
+/// Options for standard (non-streaming) archive creation
+#[derive(Debug, Clone, Default)]
+pub struct CompressOptions {
+    /// Password for AES-256 encryption (`None` for an unencrypted archive)
+    pub password: Option<String>,
+    /// Number of worker threads (0 = let the SDK choose)
+    pub num_threads: u32,
+    /// Encrypt the archive header (filenames, sizes, folder layout) as well
+    /// as file contents, equivalent to 7-Zip's `-mhe=on`. Requires `password`
+    /// to be set; [`SevenZip::list`] or [`SevenZip::test_archive`] on the
+    /// resulting archive without the password then fails with
+    /// [`crate::Error::PasswordRequired`] instead of enumerating entries.
+    pub encrypt_headers: bool,
+    /// Compression codec to use for this archive
+    pub method: CompressionMethod,
+    /// PPMd tuning, used only when `method` is [`CompressionMethod::Ppmd`]
+    pub ppmd: Option<PpmdOptions>,
+    /// BZip2 tuning, used only when `method` is [`CompressionMethod::Bzip2`]
+    pub bzip2: Option<Bzip2Options>,
+    /// LZMA2 tuning, used only when `method` is [`CompressionMethod::Lzma2`]
+    pub lzma2: Option<Lzma2Options>,
+}
+
+/// Progress callback for simple byte-based operations: `(completed, total)`
+pub type ProgressCallback = Box<dyn Fn(u64, u64) + Send>;
+
+/// Progress callback for streaming operations, with per-file granularity:
+/// `(total_processed, total_bytes, file_processed, file_total, file_name)`
+///
+/// Superseded by [`StreamProgressCallback`] for
+/// [`SevenZip::create_archive_streaming`], which reports structured
+/// [`ProgressEvent`]s instead; kept as a type for any external callers still
+/// referencing it.
+pub type BytesProgressCallback = Box<dyn Fn(u64, u64, u64, u64, &str) + Send>;
+
+/// A structured progress event from [`SevenZip::create_archive_streaming`]
+#[derive(Debug, Clone)]
+pub enum ProgressEvent {
+    /// A rolling byte-count update from the native encoder
+    ///
+    /// `total_in` is the cumulative bytes of input processed so far and
+    /// `total_out` is the total expected for the whole operation. The
+    /// native progress hook reports only one `(completed, total)` counter
+    /// pair — it doesn't separately expose compressed output size — so
+    /// both fields are derived from that same call.
+    BytesProcessed {
+        /// Cumulative input bytes processed so far
+        total_in: u64,
+        /// Total input bytes expected for the whole operation
+        total_out: u64,
+    },
+    /// About to archive this input path
+    ///
+    /// Emitted once per entry of `input_paths`, in order, before the native
+    /// encoder's single blocking call begins — the encoder has no per-file
+    /// checkpoint mid-call, so these arrive as a batch up front rather than
+    /// interleaved with `BytesProcessed`.
+    FileStarted {
+        /// The input path about to be archived
+        path: PathBuf,
+    },
+    /// A multi-volume output file has begun
+    ///
+    /// The native encoder doesn't expose a per-volume-start checkpoint
+    /// mid-call, so this (and the matching `VolumeFinished`) are emitted
+    /// together, back to back, once the whole archive has finished and its
+    /// volumes can be discovered on disk.
+    VolumeStarted {
+        /// 1-based volume index (matches the `.NNN` suffix)
+        index: u32,
+        /// Path of this volume
+        path: PathBuf,
+    },
+    /// A multi-volume output file has been fully written
+    VolumeFinished {
+        /// 1-based volume index (matches the `.NNN` suffix)
+        index: u32,
+        /// Path of this volume
+        path: PathBuf,
+        /// Size of this volume in bytes
+        size: u64,
+    },
+}
+
+/// Callback for [`SevenZip::create_archive_streaming`]'s structured progress events
+pub type StreamProgressCallback = Box<dyn FnMut(ProgressEvent) + Send>;
+
+/// Options for streaming / multi-volume archive creation
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// Split the archive into volumes of this size in bytes (0 = single volume)
+    pub split_size: u64,
+    /// Size of each read/compress chunk in bytes
+    pub chunk_size: usize,
+    /// Number of worker threads (0 = let the SDK choose)
+    pub num_threads: u32,
+    /// Password for AES-256 encryption (`None` for an unencrypted archive)
+    pub password: Option<String>,
+    /// Compression codec to use for this archive
+    pub method: CompressionMethod,
+    /// PPMd tuning, used only when `method` is [`CompressionMethod::Ppmd`]
+    pub ppmd: Option<PpmdOptions>,
+    /// BZip2 tuning, used only when `method` is [`CompressionMethod::Bzip2`]
+    pub bzip2: Option<Bzip2Options>,
+    /// LZMA2 tuning, used only when `method` is [`CompressionMethod::Lzma2`]
+    pub lzma2: Option<Lzma2Options>,
+    /// Encrypt the archive header (filenames, sizes, folder layout) as well
+    /// as file contents, equivalent to 7-Zip's `-mhe=on`. Requires `password`
+    /// to be set; listing or testing the archive without the password then
+    /// fails with [`crate::Error::PasswordRequired`] instead of enumerating entries.
+    pub encrypt_headers: bool,
+    /// Symlink/permission/timestamp handling for this archive
+    pub metadata: MetadataOptions,
+    /// Content-defined chunk and deduplicate `input_paths` before archiving
+    ///
+    /// When set, [`SevenZip::create_archive_streaming`] runs every input
+    /// through [`crate::dedup::dedup_files`] first and archives the
+    /// deduplicated chunk pool plus a reassembly manifest instead of the
+    /// original files, so identical content shared across inputs (or
+    /// repeated within one of them) is stored only once. All entries in
+    /// `input_paths` must be regular files when this is enabled; a
+    /// directory entry fails with [`crate::Error::InvalidParameter`]. Use
+    /// [`SevenZip::extract_streaming_dedup`], not plain
+    /// [`SevenZip::extract`], to get the original files back out.
+    pub dedup: bool,
+    /// Override `method` to [`CompressionMethod::Copy`] if `input_paths`
+    /// look incompressible
+    ///
+    /// The underlying encoder applies a single method to the whole archive
+    /// (there's no per-entry codec in this SDK's multi-volume write path),
+    /// so this doesn't give each file its own codec; instead, when set,
+    /// [`SevenZip::create_archive_streaming`] samples every input with
+    /// [`looks_incompressible`] and, if *all* of them look incompressible,
+    /// archives the whole batch with `Copy` instead of `method` to avoid
+    /// spending CPU compressing data (e.g. already-compressed media, or
+    /// pseudo-random test data) that won't shrink.
+    pub per_file: bool,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            split_size: 0,
+            chunk_size: 1024 * 1024,
+            num_threads: 0,
+            password: None,
+            method: CompressionMethod::default(),
+            ppmd: None,
+            bzip2: None,
+            lzma2: None,
+            encrypt_headers: false,
+            metadata: MetadataOptions::default(),
+            dedup: false,
+            per_file: false,
+        }
+    }
+}
+
+/// Cheap incompressibility heuristic: sample up to 64KB of `path` and
+/// report `true` if more than 90% of the 256 possible byte values occur in
+/// the sample
+///
+/// Highly compressible data (text, bitmaps, anything with repeated bytes or
+/// runs) has far fewer distinct byte values in a sample than random or
+/// already-compressed data, so this avoids the cost of actually running a
+/// codec over the file just to measure whether it was worth it.
+fn looks_incompressible(path: &Path) -> Result<bool> {
+    use std::io::Read as _;
+    const SAMPLE_SIZE: usize = 64 * 1024;
+    let mut file = std::fs::File::open(path)?;
+    let mut buf = vec![0u8; SAMPLE_SIZE];
+    let mut total_read = 0;
+    loop {
+        let n = file.read(&mut buf[total_read..])?;
+        if n == 0 {
+            break;
+        }
+        total_read += n;
+        if total_read == buf.len() {
+            break;
+        }
+    }
+    if total_read == 0 {
+        return Ok(false);
+    }
+    let sample = &buf[..total_read];
+    let mut seen = [false; 256];
+    let mut distinct = 0usize;
+    for &byte in sample {
+        if !seen[byte as usize] {
+            seen[byte as usize] = true;
+            distinct += 1;
+        }
+    }
+    Ok((distinct as f64 / 256.0) > 0.9 && (distinct as f64 / sample.len().min(256) as f64) > 0.9)
+}
+
+const fn build_crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 { 0xEDB88320 ^ (crc >> 1) } else { crc >> 1 };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+// `pub(crate)` so `crate::zip_format`'s legacy ZipCrypto decoder can reuse the
+// same table for its own per-byte CRC32 update step.
+pub(crate) const CRC32_TABLE: [u32; 256] = build_crc32_table();
+
+/// Fold `data` into an in-progress CRC32 accumulator
+///
+/// `crc` starts at `0xFFFFFFFF` for a fresh checksum and is threaded through
+/// successive calls as more data arrives (e.g. one call per decoded chunk in
+/// [`bounded_extract_trampoline`]); finish with `!crc` once the last chunk
+/// has been folded in, same as [`crc32_ieee`] does internally.
+fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        let index = ((crc ^ byte as u32) & 0xFF) as usize;
+        crc = CRC32_TABLE[index] ^ (crc >> 8);
+    }
+    crc
+}
+
+/// IEEE CRC32 (the polynomial used by zip/gzip/7z headers), table-driven
+///
+/// `pub(crate)` so [`crate::segment`]'s per-chunk checksum can reuse the
+/// same implementation rather than duplicating the table-driven loop.
+pub(crate) fn crc32_ieee(data: &[u8]) -> u32 {
+    !crc32_update(0xFFFFFFFF, data)
+}
+
+/// Convert a C error code into an [`Error`], giving
+/// [`ffi::SevenZipErrorCode::SEVENZIP_ERROR_PASSWORD_REQUIRED`] a message
+/// that distinguishes "no password was supplied" from "the supplied
+/// password didn't decrypt the header", instead of [`Error::from_code`]'s
+/// generic wording
+fn header_error(code: ffi::SevenZipErrorCode, password: Option<&str>) -> Error {
+    if code == ffi::SevenZipErrorCode::SEVENZIP_ERROR_PASSWORD_REQUIRED {
+        return match password {
+            Some(_) => Error::WrongPassword(
+                "archive has encrypted headers; the supplied password did not decrypt them".to_string(),
+            ),
+            None => Error::PasswordRequired(
+                "archive has encrypted headers; a password is required".to_string(),
+            ),
+        };
+    }
+    // The C library has no separate error code for "headers decoded fine,
+    // but content decryption/decode failed" — the far more common case than
+    // encrypted headers, since most archives only encrypt content. That
+    // failure surfaces as the generic SEVENZIP_ERROR_EXTRACT instead, which
+    // would otherwise report as an opaque Error::Extract even though a
+    // wrong password is by far the likeliest cause whenever one was
+    // supplied. Only applies when a password was actually given — with none
+    // supplied, an extract failure is genuine corruption, not a guess at a
+    // missing password.
+    if code == ffi::SevenZipErrorCode::SEVENZIP_ERROR_EXTRACT && password.is_some() {
+        return Error::WrongPassword(
+            "extraction failed with a password supplied; the password is likely incorrect".to_string(),
+        );
+    }
+    Error::from_code(code)
+}
+
+fn path_to_str(path: &Path) -> Result<&str> {
+    path.to_str()
+        .ok_or_else(|| Error::Io("Invalid path encoding".to_string()))
+}
+
+fn paths_to_c_strings(paths: &[impl AsRef<Path>]) -> Result<Vec<CString>> {
+    paths
+        .iter()
+        .map(|p| {
+            let s = path_to_str(p.as_ref())?;
+            Ok(CString::new(s)?)
+        })
+        .collect()
+}
+
+fn c_string_ptrs(strings: &[CString]) -> Vec<*const c_char> {
+    let mut ptrs: Vec<*const c_char> = strings.iter().map(|s| s.as_ptr()).collect();
+    ptrs.push(std::ptr::null());
+    ptrs
+}
+
+extern "C" fn progress_trampoline(completed: u64, total: u64, user_data: *mut c_void) {
+    if user_data.is_null() {
+        return;
+    }
+    let callback = unsafe { &*(user_data as *const ProgressCallback) };
+    callback(completed, total);
+}
+
+extern "C" fn stream_progress_trampoline(completed: u64, total: u64, user_data: *mut c_void) {
+    if user_data.is_null() {
+        return;
+    }
+    let callback = unsafe { &mut *(user_data as *mut StreamProgressCallback) };
+    callback(ProgressEvent::BytesProcessed {
+        total_in: completed,
+        total_out: total,
+    });
+}
+
+extern "C" fn extract_data_trampoline(data: *const u8, len: usize, user_data: *mut c_void) -> i32 {
+    if data.is_null() || user_data.is_null() {
+        return 0;
+    }
+    let buf = unsafe { &mut *(user_data as *mut Vec<u8>) };
+    let chunk = unsafe { std::slice::from_raw_parts(data, len) };
+    buf.extend_from_slice(chunk);
+    0
+}
+
+/// Per-entry state threaded through [`bounded_extract_trampoline`] by
+/// [`SevenZip::extract_entry_bounded`]
+///
+/// Each decoded chunk is written straight to `file` instead of accumulating
+/// in a `Vec`, and `*extracted_total` (the running total across every entry
+/// [`SevenZip::extract_secure`] has written so far) is checked against
+/// `max_total_size` after every chunk - so a header that understates an
+/// entry's real uncompressed size is caught as soon as the overrun happens,
+/// not after the whole (potentially enormous) entry has already been
+/// decoded into memory.
+struct BoundedExtractState<'a> {
+    file: std::fs::File,
+    extracted_total: &'a mut u64,
+    max_total_size: u64,
+    limit_exceeded: bool,
+    io_error: Option<std::io::Error>,
+    verify_crc: bool,
+    crc: u32,
+}
+
+extern "C" fn bounded_extract_trampoline(data: *const u8, len: usize, user_data: *mut c_void) -> i32 {
+    if data.is_null() || user_data.is_null() {
+        return 0;
+    }
+    let state = unsafe { &mut *(user_data as *mut BoundedExtractState) };
+    let chunk = unsafe { std::slice::from_raw_parts(data, len) };
+
+    *state.extracted_total += chunk.len() as u64;
+    if state.max_total_size != 0 && *state.extracted_total > state.max_total_size {
+        state.limit_exceeded = true;
+        return 1;
+    }
+
+    if state.verify_crc {
+        state.crc = crc32_update(state.crc, chunk);
+    }
+
+    if let Err(e) = state.file.write_all(chunk) {
+        state.io_error = Some(e);
+        return 1;
+    }
+    0
+}
+
+extern "C" fn update_entry_trampoline(name: *const c_char, was_recompressed: i32, user_data: *mut c_void) {
+    if name.is_null() || user_data.is_null() {
+        return;
+    }
+    let entries = unsafe { &mut *(user_data as *mut Vec<(String, UpdateAction)>) };
+    let name = unsafe { CStr::from_ptr(name) }.to_string_lossy().to_string();
+    let action = if was_recompressed != 0 {
+        UpdateAction::Recompressed
+    } else {
+        UpdateAction::Copied
+    };
+    entries.push((name, action));
+}
+
+/// Maximum entry name length read per [`ListIter`] step
+const LIST_NAME_BUFFER_SIZE: usize = 4096;
+
+/// A pull-based iterator over archive entries
+///
+/// Unlike [`SevenZip::list`], which materializes the whole listing into a
+/// `Vec` up front, `ListIter` asks the native archive reader for one entry
+/// at a time, so callers can stop early (e.g. after finding a match) without
+/// paying to parse the rest of a multi-gigabyte header.
+///
+/// The underlying native handle is released when the iterator is dropped,
+/// whether or not it was fully exhausted.
+pub struct ListIter {
+    handle: *mut c_void,
+    // Kept alive for the duration of the handle: the native side may hold
+    // onto the password pointer for the lifetime of the open archive.
+    _password: Option<CString>,
+    done: bool,
+}
+
+impl ListIter {
+    fn open(archive_path: &Path, password: Option<&str>) -> Result<Self> {
+        let c_archive = CString::new(path_to_str(archive_path)?)?;
+        let c_password = password.map(CString::new).transpose()?;
+        let mut handle: *mut c_void = std::ptr::null_mut();
+
+        unsafe {
+            let result = ffi::sevenzip_list_open(
+                c_archive.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                &mut handle,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(header_error(result, password));
+            }
+        }
+
+        Ok(Self {
+            handle,
+            _password: c_password,
+            done: false,
+        })
+    }
+}
+
+impl Iterator for ListIter {
+    type Item = Result<ArchiveEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut name_buf = vec![0 as c_char; LIST_NAME_BUFFER_SIZE];
+        let mut size: u64 = 0;
+        let mut packed_size: u64 = 0;
+        let mut is_dir: i32 = 0;
+        let mut crc32: u32 = 0;
+        let mut has_crc32: i32 = 0;
+        let mut method: i32 = -1;
+        let mut has_more: i32 = 0;
+
+        let result = unsafe {
+            ffi::sevenzip_list_next(
+                self.handle,
+                name_buf.as_mut_ptr(),
+                name_buf.len(),
+                &mut size,
+                &mut packed_size,
+                &mut is_dir,
+                &mut crc32,
+                &mut has_crc32,
+                &mut method,
+                &mut has_more,
+            )
+        };
+
+        if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+            self.done = true;
+            return Some(Err(Error::from_code(result)));
+        }
+
+        if has_more == 0 {
+            self.done = true;
+            return None;
+        }
+
+        let name = unsafe { CStr::from_ptr(name_buf.as_ptr()) }
+            .to_string_lossy()
+            .to_string();
+
+        Some(Ok(ArchiveEntry {
+            name,
+            size,
+            packed_size,
+            is_dir: is_dir != 0,
+            crc32: if has_crc32 != 0 { Some(crc32) } else { None },
+            method: CompressionMethod::from_i32(method),
+        }))
+    }
+}
+
+impl Drop for ListIter {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe { ffi::sevenzip_list_close(self.handle) };
+        }
+    }
+}
+
+// `ListIter` owns its native handle exclusively and never shares it, so it
+// is safe to move (and use) across threads.
+unsafe impl Send for ListIter {}
+
+static STREAM_TEMP_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn unique_stream_temp_path() -> PathBuf {
+    let n = STREAM_TEMP_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    std::env::temp_dir().join(format!("seven_zip_stream_{}_{}.7z", std::process::id(), n))
+}
+
+static COPY_ENTRIES_TEMP_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn unique_copy_entries_temp_dir() -> PathBuf {
+    let n = COPY_ENTRIES_TEMP_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    std::env::temp_dir().join(format!("seven_zip_copy_entries_{}_{}", std::process::id(), n))
+}
+
+static DEDUP_TEMP_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn unique_dedup_temp_dir() -> PathBuf {
+    let n = DEDUP_TEMP_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    std::env::temp_dir().join(format!("seven_zip_dedup_{}_{}", std::process::id(), n))
+}
+
+/// Stage `input_paths` as a deduplicated chunk pool plus a reassembly
+/// manifest under a fresh temp directory, returning that directory's path
+///
+/// Every entry in `input_paths` must be a regular file. Used by
+/// [`SevenZip::create_archive_streaming`] when [`StreamOptions::dedup`] is set.
+fn stage_dedup_temp_dir(input_paths: &[impl AsRef<Path>]) -> Result<PathBuf> {
+    let mut files = Vec::with_capacity(input_paths.len());
+    for path in input_paths {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(Error::InvalidParameter(format!(
+                "dedup requires regular files, got a non-file path: {}",
+                path.display()
+            )));
+        }
+        let data = std::fs::read(path)?;
+        files.push((path.to_path_buf(), data));
+    }
+
+    let result = dedup::dedup_files(&files, &dedup::ChunkerConfig::default());
+
+    let temp_dir = unique_dedup_temp_dir();
+    std::fs::create_dir_all(&temp_dir)?;
+    for chunk in &result.unique_chunks {
+        // Named by content hash, not sequential index, so
+        // `extract_streaming_dedup` can look a chunk up straight from the
+        // hash recorded in `manifest.txt` (see [`dedup::chunk_file_name`])
+        // without needing a separate index-to-hash mapping.
+        std::fs::write(temp_dir.join(dedup::chunk_file_name(&chunk.hash)), &chunk.data)?;
+    }
+    std::fs::write(temp_dir.join("manifest.txt"), result.manifest_text())?;
+
+    Ok(temp_dir)
+}
+
+/// Recursively find a file named `name` under `dir`, returning its path
+///
+/// Used by [`SevenZip::extract_streaming_dedup`] to locate `manifest.txt`
+/// inside the extracted archive, which nests it (and the chunk pool
+/// alongside it) under whatever directory name [`stage_dedup_temp_dir`]
+/// picked at creation time.
+fn find_file_named(dir: &Path, name: &str) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(found) = find_file_named(&path, name) {
+                return Some(found);
+            }
+        } else if path.file_name().and_then(|n| n.to_str()) == Some(name) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// A 7z archive opened from a non-seekable byte stream
+///
+/// 7z normally seeks to an end-of-archive header to locate its directory, so
+/// [`SevenZip::open_stream`] buffers the whole source to a private temp file
+/// up front (the only way to get random access out of something like a
+/// socket or stdin), then drives extraction over that file exactly like a
+/// path-based archive. The temp file is removed when this value is dropped.
+pub struct StreamArchive {
+    sz: SevenZip,
+    temp_path: PathBuf,
+    password: Option<String>,
+}
+
+impl StreamArchive {
+    /// Iterate the archive's entries in storage order, decoding each one's
+    /// content on demand
+    pub fn entries(&self) -> Result<StreamEntryIter<'_>> {
+        let inner = self.sz.list_iter(&self.temp_path, self.password.as_deref())?;
+        Ok(StreamEntryIter { archive: self, inner })
+    }
+}
+
+impl Drop for StreamArchive {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.temp_path);
+    }
+}
+
+/// One entry yielded by [`StreamEntryIter`]
+///
+/// Implements [`Read`] over the entry's already-decoded content; directory
+/// entries read as empty.
+pub struct StreamEntry {
+    /// Entry path within the archive
+    pub name: String,
+    /// Uncompressed size in bytes
+    pub size: u64,
+    /// Compressed (packed) size in bytes
+    pub packed_size: u64,
+    /// Whether this entry is a directory
+    pub is_dir: bool,
+    data: Cursor<Vec<u8>>,
+}
+
+impl Read for StreamEntry {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.data.read(buf)
+    }
+}
+
+/// Iterator over the entries of a [`StreamArchive`]
+pub struct StreamEntryIter<'a> {
+    archive: &'a StreamArchive,
+    inner: ListIter,
+}
+
+impl Iterator for StreamEntryIter<'_> {
+    type Item = Result<StreamEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = match self.inner.next()? {
+            Ok(entry) => entry,
+            Err(err) => return Some(Err(err)),
+        };
+
+        if entry.is_dir {
+            return Some(Ok(StreamEntry {
+                name: entry.name,
+                size: entry.size,
+                packed_size: entry.packed_size,
+                is_dir: true,
+                data: Cursor::new(Vec::new()),
+            }));
+        }
+
+        let data = match self.archive.sz.extract_to_memory(
+            &self.archive.temp_path,
+            &entry.name,
+            self.archive.password.as_deref(),
+        ) {
+            Ok(data) => data,
+            Err(err) => return Some(Err(err)),
+        };
+
+        Some(Ok(StreamEntry {
+            name: entry.name,
+            size: entry.size,
+            packed_size: entry.packed_size,
+            is_dir: false,
+            data: Cursor::new(data),
+        }))
+    }
+}
+
+/// Entry point for all 7z archive operations
+///
+/// `SevenZip` is a thin, stateless handle around the underlying C library.
+/// It can be created cheaply and shared across operations.
+pub struct SevenZip;
+
+impl SevenZip {
+    /// Initialize the 7z library
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    fn compress_options(
+        password: Option<&str>,
+        num_threads: u32,
+        method: CompressionMethod,
+        ppmd: Option<PpmdOptions>,
+        bzip2: Option<Bzip2Options>,
+        lzma2: Option<Lzma2Options>,
+        encrypt_headers: bool,
+        metadata: MetadataOptions,
+    ) -> Result<(Option<CString>, ffi::SevenZipCompressOptions)> {
+        if encrypt_headers && password.is_none() {
+            return Err(Error::InvalidParameter(
+                "encrypt_headers requires a password".to_string(),
+            ));
+        }
+        let c_password = password.map(CString::new).transpose()?;
+        let options = ffi::SevenZipCompressOptions {
+            num_threads,
+            dict_size: lzma2.map_or(0, |o| o.dict_size),
+            solid: 1,
+            password: c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+            method: method.into(),
+            ppmd_order: ppmd.map_or(0, |o| o.order),
+            ppmd_mem_mb: ppmd.map_or(0, |o| o.mem_mb),
+            bzip2_block_size: bzip2.map_or(0, |o| o.block_size),
+            encrypt_headers: encrypt_headers as i32,
+            store_symlinks: metadata.store_symlinks as i32,
+            preserve_permissions: metadata.preserve_permissions as i32,
+            preserve_timestamps: metadata.preserve_timestamps as i32,
+        };
+        Ok((c_password, options))
+    }
+
+    /// Create a standard 7z archive from the given input paths
+    ///
+    /// Each path may be a file or a directory; a directory is walked
+    /// recursively and stored with its relative folder structure intact
+    /// (including entries for empty subdirectories), so an entry extracted
+    /// later as e.g. `logs/2024/app.txt` came from `logs/2024/app.txt`
+    /// under the input directory, not a flattened copy of it.
+    pub fn create_archive(
+        &self,
+        archive_path: impl AsRef<Path>,
+        input_paths: &[impl AsRef<Path>],
+        level: CompressionLevel,
+        options: Option<&CompressOptions>,
+    ) -> Result<()> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+        let c_paths = paths_to_c_strings(input_paths)?;
+        let path_ptrs = c_string_ptrs(&c_paths);
+
+        let password = options.and_then(|o| o.password.as_deref());
+        let num_threads = options.map_or(0, |o| o.num_threads);
+        let encrypt_headers = options.map_or(false, |o| o.encrypt_headers);
+        let method = options.map_or(CompressionMethod::default(), |o| o.method);
+        let ppmd = options.and_then(|o| o.ppmd);
+        let bzip2 = options.and_then(|o| o.bzip2);
+        let lzma2 = options.and_then(|o| o.lzma2);
+        let (_c_password, c_options) = Self::compress_options(
+            password,
+            num_threads,
+            method,
+            ppmd,
+            bzip2,
+            lzma2,
+            encrypt_headers,
+            MetadataOptions::default(),
+        )?;
+
+        unsafe {
+            let result = ffi::sevenzip_create_archive(
+                c_archive.as_ptr(),
+                path_ptrs.as_ptr(),
+                level.into(),
+                &c_options,
+                None,
+                std::ptr::null_mut(),
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(Error::from_code(result));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add or replace entries in an existing `.7z` archive in place
+    ///
+    /// Entries in `new_files` that are new or whose content changed are
+    /// recompressed; everything else has its packed stream copied verbatim
+    /// into the rewritten header rather than being re-encoded. The returned
+    /// vector reports which action was taken per entry, in native order, so
+    /// callers can confirm the fast path engaged instead of a full rebuild.
+    pub fn update_archive(
+        &self,
+        archive_path: impl AsRef<Path>,
+        new_files: &[impl AsRef<Path>],
+        level: CompressionLevel,
+        options: Option<&CompressOptions>,
+    ) -> Result<Vec<(String, UpdateAction)>> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+        let c_paths = paths_to_c_strings(new_files)?;
+        let path_ptrs = c_string_ptrs(&c_paths);
+
+        let password = options.and_then(|o| o.password.as_deref());
+        let num_threads = options.map_or(0, |o| o.num_threads);
+        let encrypt_headers = options.map_or(false, |o| o.encrypt_headers);
+        let method = options.map_or(CompressionMethod::default(), |o| o.method);
+        let ppmd = options.and_then(|o| o.ppmd);
+        let bzip2 = options.and_then(|o| o.bzip2);
+        let lzma2 = options.and_then(|o| o.lzma2);
+        let (_c_password, c_options) = Self::compress_options(
+            password,
+            num_threads,
+            method,
+            ppmd,
+            bzip2,
+            lzma2,
+            encrypt_headers,
+            MetadataOptions::default(),
+        )?;
+
+        let mut entries: Vec<(String, UpdateAction)> = Vec::new();
+
+        unsafe {
+            let result = ffi::sevenzip_update_archive(
+                c_archive.as_ptr(),
+                path_ptrs.as_ptr(),
+                level.into(),
+                &c_options,
+                Some(update_entry_trampoline),
+                &mut entries as *mut Vec<(String, UpdateAction)> as *mut c_void,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(Error::from_code(result));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Copy `entry_names` from `src_archive` into `dst_archive`, merging them
+    /// in via [`SevenZip::update_archive`]
+    ///
+    /// The bundled C library has no API for splicing an already-compressed
+    /// substream directly into another archive's folder structure, so this
+    /// is not the zero-recompression copy its name might suggest: each entry
+    /// is decoded in memory with [`SevenZip::extract_to_memory`] and then
+    /// recompressed as part of the update. It still saves the caller from
+    /// manually staging files on disk to merge archives.
+    pub fn copy_entries(
+        &self,
+        src_archive: impl AsRef<Path>,
+        dst_archive: impl AsRef<Path>,
+        entry_names: &[&str],
+        level: CompressionLevel,
+    ) -> Result<Vec<(String, UpdateAction)>> {
+        let src_archive = src_archive.as_ref();
+        let temp_dir = unique_copy_entries_temp_dir();
+        std::fs::create_dir_all(&temp_dir)?;
+
+        for name in entry_names {
+            let data = self.extract_to_memory(src_archive, name, None)?;
+            let out_path = temp_dir.join(name);
+            if let Some(parent) = out_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&out_path, data)?;
+        }
+
+        let result = self.update_archive(dst_archive, &[&temp_dir], level, None);
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        result
+    }
+
+    /// Open a `.7z` archive from an arbitrary non-seekable byte source (a
+    /// socket, a pipe, stdin, ...)
+    ///
+    /// `reader` is read to completion and buffered into a private temp file,
+    /// since the native reader needs random access to locate the header at
+    /// the end of the archive; [`StreamArchive::entries`] then drives
+    /// extraction over that file exactly like [`SevenZip::list_iter`] would
+    /// over a path. Returns [`Error::NotSeekable`] if `reader` yielded no
+    /// data at all, since there is then no header to locate.
+    pub fn open_stream(&self, mut reader: impl Read, password: Option<&str>) -> Result<StreamArchive> {
+        let temp_path = unique_stream_temp_path();
+        let mut file = std::fs::File::create(&temp_path)?;
+        let copied = std::io::copy(&mut reader, &mut file)?;
+        drop(file);
+
+        if copied == 0 {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(Error::NotSeekable(
+                "stream yielded no data; no archive header to locate".to_string(),
+            ));
+        }
+
+        Ok(StreamArchive {
+            sz: SevenZip,
+            temp_path,
+            password: password.map(String::from),
+        })
+    }
+
+    /// Create a single-entry `.7z` archive from an in-memory (or piped)
+    /// byte source, named `entry_name` inside the archive
+    ///
+    /// Like [`SevenZip::open_stream`], this buffers `reader` to a private
+    /// temp file first: the C library's encoder only takes file paths, and
+    /// there is no `ISeekInStream` shim here to scan for header bytes since
+    /// we aren't parsing the 7z format ourselves, just staging input for it.
+    pub fn create_archive_from_reader(
+        &self,
+        archive_path: impl AsRef<Path>,
+        entry_name: &str,
+        mut reader: impl Read,
+        level: CompressionLevel,
+        options: Option<&CompressOptions>,
+    ) -> Result<()> {
+        let temp_dir = unique_copy_entries_temp_dir();
+        std::fs::create_dir_all(&temp_dir)?;
+        let staged = temp_dir.join(entry_name);
+        if let Some(parent) = staged.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(&staged)?;
+        std::io::copy(&mut reader, &mut file)?;
+        drop(file);
+
+        let result = self.create_archive(archive_path, &[&staged], level, options);
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        result
+    }
+
+    /// Create a `.7z` archive from `inputs` and copy the resulting bytes
+    /// into `writer`
+    ///
+    /// Like [`SevenZip::create_archive_from_reader`], this stages the real
+    /// archive in a private temp file (the C encoder only writes to paths)
+    /// and then streams it out; `writer` need not be seekable.
+    pub fn create_archive_to_writer(
+        &self,
+        inputs: &[impl AsRef<Path>],
+        mut writer: impl Write,
+        level: CompressionLevel,
+        options: Option<&CompressOptions>,
+    ) -> Result<()> {
+        let temp_path = unique_stream_temp_path();
+        self.create_archive(&temp_path, inputs, level, options)?;
+        let result = (|| -> Result<()> {
+            let mut file = std::fs::File::open(&temp_path)?;
+            std::io::copy(&mut file, &mut writer)?;
+            Ok(())
+        })();
+        let _ = std::fs::remove_file(&temp_path);
+        result
+    }
+
+    /// List the contents of a `.7z` archive read from an arbitrary byte
+    /// source rather than a file path
+    ///
+    /// Buffers `reader` to a private temp file first, same rationale as
+    /// [`SevenZip::open_stream`]. Prefer `open_stream` when the source is
+    /// large and entries should be read lazily; this is the simpler
+    /// all-at-once form, mirroring [`SevenZip::list`].
+    pub fn list_from_reader(&self, reader: impl Read, password: Option<&str>) -> Result<Vec<ArchiveEntry>> {
+        let temp_path = unique_stream_temp_path();
+        let mut file = std::fs::File::create(&temp_path)?;
+        let mut reader = reader;
+        std::io::copy(&mut reader, &mut file)?;
+        drop(file);
+
+        let result = self.list(&temp_path, password);
+        let _ = std::fs::remove_file(&temp_path);
+        result
+    }
+
+    /// Extract a `.7z` archive read from an arbitrary byte source into
+    /// `output_dir`
+    ///
+    /// Buffers `reader` to a private temp file first, same rationale as
+    /// [`SevenZip::open_stream`], then delegates to
+    /// [`SevenZip::extract_with_password`] over that file.
+    pub fn extract_from_reader(
+        &self,
+        reader: impl Read,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let temp_path = unique_stream_temp_path();
+        let mut file = std::fs::File::create(&temp_path)?;
+        let mut reader = reader;
+        std::io::copy(&mut reader, &mut file)?;
+        drop(file);
+
+        let result = self.extract_with_password(&temp_path, output_dir, password, None);
+        let _ = std::fs::remove_file(&temp_path);
+        result
+    }
+
+    /// Extract an archive, without a password, to `output_dir`
+    pub fn extract(&self, archive_path: impl AsRef<Path>, output_dir: impl AsRef<Path>) -> Result<()> {
+        self.extract_with_password(archive_path, output_dir, None, None)
+    }
+
+    /// Extract an archive, optionally password-protected, reporting progress
+    ///
+    /// If the archive's headers are encrypted, a missing password returns
+    /// [`Error::PasswordRequired`] and a supplied-but-wrong password returns
+    /// [`Error::WrongPassword`] instead of the generic
+    /// [`Error::InvalidArchive`]/[`Error::Extract`] a corrupted archive would
+    /// produce, mirroring [`SevenZip::list`]'s `header_error` handling.
+    pub fn extract_with_password(
+        &self,
+        archive_path: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+        progress: Option<ProgressCallback>,
+    ) -> Result<()> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+        let c_output = CString::new(path_to_str(output_dir.as_ref())?)?;
+        let c_password = password.map(CString::new).transpose()?;
+
+        let boxed_progress = progress.map(Box::new);
+        let user_data = boxed_progress
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |cb| cb.as_ref() as *const ProgressCallback as *mut c_void);
+        let callback = if boxed_progress.is_some() {
+            Some(progress_trampoline as ffi::ProgressCallbackFn)
+        } else {
+            None
+        };
+
+        unsafe {
+            let result = ffi::sevenzip_extract(
+                c_archive.as_ptr(),
+                c_output.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                callback,
+                user_data,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(header_error(result, password));
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode a single entry straight to `dest_path`, one chunk at a time,
+    /// checking `*extracted_total` against `max_total_size` after every
+    /// chunk instead of after the whole entry has been decoded
+    ///
+    /// Used by [`SevenZip::extract_secure`] in place of
+    /// [`SevenZip::extract_to_memory`] so a header that understates an
+    /// entry's real uncompressed size is caught mid-decode - the C library
+    /// streams decoded chunks to [`bounded_extract_trampoline`] one at a
+    /// time (see [`ffi::ExtractDataCallbackFn`]), so at most one chunk is
+    /// ever held in memory. `dest_path` is removed on any failure (limit
+    /// exceeded, I/O error, or the extract call itself failing) so a failed
+    /// entry never leaves a partial file behind; on success, returns the
+    /// entry's CRC32 so the caller can check it without re-reading the file.
+    fn extract_entry_bounded(
+        &self,
+        archive_path: &Path,
+        entry_name: &str,
+        password: Option<&str>,
+        dest_path: &Path,
+        extracted_total: &mut u64,
+        max_total_size: u64,
+        verify_crc: bool,
+    ) -> Result<u32> {
+        let c_archive = CString::new(path_to_str(archive_path)?)?;
+        let c_entry = CString::new(entry_name)?;
+        let c_password = password.map(CString::new).transpose()?;
+        let file = std::fs::File::create(dest_path)?;
+
+        let mut state = BoundedExtractState {
+            file,
+            extracted_total,
+            max_total_size,
+            limit_exceeded: false,
+            io_error: None,
+            verify_crc,
+            crc: 0xFFFFFFFF,
+        };
+
+        let result = unsafe {
+            ffi::sevenzip_extract_entry_to_memory(
+                c_archive.as_ptr(),
+                c_entry.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                bounded_extract_trampoline,
+                &mut state as *mut BoundedExtractState as *mut c_void,
+            )
+        };
+
+        if let Some(e) = state.io_error.take() {
+            let _ = std::fs::remove_file(dest_path);
+            return Err(Error::from(e));
+        }
+        if state.limit_exceeded {
+            let _ = std::fs::remove_file(dest_path);
+            return Err(Error::UnsafeArchive(format!(
+                "extracted size exceeded the limit of {} bytes while writing '{}'",
+                max_total_size, entry_name
+            )));
+        }
+        if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+            let _ = std::fs::remove_file(dest_path);
+            return Err(header_error(result, password));
+        }
+        Ok(!state.crc)
+    }
+
+    /// Extract an archive entry by entry, rejecting Zip-Slip-style path
+    /// traversal, enforcing [`ExtractOptions`]'s size/entry-count budget, and
+    /// applying its `strip_components`/`dest_prefix` path rewrite
+    ///
+    /// Unlike [`SevenZip::extract`], which hands the whole job to the native
+    /// extractor, this walks [`SevenZip::list`]'s entries itself: each
+    /// entry's stored path is resolved with [`sanitized_entry_path`] (a
+    /// root/prefix component or a `..` parent component fails the whole
+    /// call before anything is written, and this check runs *after*
+    /// `strip_components`/`dest_prefix` are applied, so neither can be used
+    /// to smuggle a path outside `output_dir`), and the running
+    /// uncompressed-byte total is checked against `options.max_total_size`
+    /// both from the listing up front and incrementally as each entry
+    /// streams in via [`SevenZip::extract_entry_bounded`], so a header that
+    /// understates an entry's real size is caught as soon as the overrun
+    /// happens rather than after the whole (potentially enormous) entry has
+    /// been decoded into memory. With `options.verify_crc` set, each entry's
+    /// content is also checksummed against its recorded CRC32 as it streams
+    /// in, returning [`Error::ChecksumMismatch`] on the first divergence.
+    /// Nothing is left at its final path once a limit is exceeded, an unsafe
+    /// path is found, or a checksum fails to match; nothing written so far
+    /// is rolled back.
+    pub fn extract_secure(
+        &self,
+        archive_path: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+        options: &ExtractOptions,
+    ) -> Result<()> {
+        let archive_path = archive_path.as_ref();
+        let output_dir = output_dir.as_ref();
+        let entries = self.list(archive_path, password)?;
+
+        if options.max_entries != 0 && entries.len() > options.max_entries {
+            return Err(Error::UnsafeArchive(format!(
+                "archive has {} entries, exceeding the limit of {}",
+                entries.len(),
+                options.max_entries
+            )));
+        }
+
+        let declared_total: u64 = entries.iter().map(|e| e.size).sum();
+        if options.max_total_size != 0 && declared_total > options.max_total_size {
+            return Err(Error::UnsafeArchive(format!(
+                "archive's declared uncompressed size ({} bytes) exceeds the limit of {} bytes",
+                declared_total, options.max_total_size
+            )));
+        }
+
+        for entry in &entries {
+            sanitized_entry_path(
+                output_dir,
+                options.dest_prefix.as_deref(),
+                options.strip_components,
+                &entry.name,
+            )?;
+        }
+
+        let mut extracted_total: u64 = 0;
+        for entry in &entries {
+            let Some(path) = sanitized_entry_path(
+                output_dir,
+                options.dest_prefix.as_deref(),
+                options.strip_components,
+                &entry.name,
+            )?
+            else {
+                // `strip_components` consumed every segment of this entry
+                // (e.g. the wrapper directory itself); nothing to write.
+                continue;
+            };
+
+            if entry.is_dir {
+                std::fs::create_dir_all(&path)?;
+                continue;
+            }
+
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            // Decoded into a sibling temp file first and only renamed into
+            // place on success, so a failing entry never leaves a partial
+            // file at `path` - the same "nothing lands at the final path
+            // unless the whole entry succeeds" contract the old
+            // fully-buffered implementation got for free from writing with
+            // `std::fs::write` only after the whole entry was in memory.
+            let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+            temp_name.push(".part");
+            let temp_path = path.with_file_name(temp_name);
+            let actual_crc = self.extract_entry_bounded(
+                archive_path,
+                &entry.name,
+                password,
+                &temp_path,
+                &mut extracted_total,
+                options.max_total_size,
+                options.verify_crc,
+            )?;
+
+            if options.verify_crc {
+                if let Some(expected) = entry.crc32 {
+                    if actual_crc != expected {
+                        let _ = std::fs::remove_file(&temp_path);
+                        return Err(Error::ChecksumMismatch { name: entry.name.clone(), expected, actual: actual_crc });
+                    }
+                }
+            }
+
+            std::fs::rename(&temp_path, &path)?;
+        }
+        Ok(())
+    }
+
+    /// Extract an archive with explicit control over symlink/permission/
+    /// timestamp restoration
+    ///
+    /// [`SevenZip::extract`] and [`SevenZip::extract_with_password`] always
+    /// restore permissions and timestamps; use this when a caller needs to
+    /// opt out (e.g. extracting into a sandbox where `chmod`/`utimensat`
+    /// would fail or aren't wanted).
+    pub fn extract_with_metadata(
+        &self,
+        archive_path: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+        metadata: MetadataOptions,
+        progress: Option<ProgressCallback>,
+    ) -> Result<()> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+        let c_output = CString::new(path_to_str(output_dir.as_ref())?)?;
+        let c_password = password.map(CString::new).transpose()?;
+
+        let boxed_progress = progress.map(Box::new);
+        let user_data = boxed_progress
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |cb| cb.as_ref() as *const ProgressCallback as *mut c_void);
+        let callback = if boxed_progress.is_some() {
+            Some(progress_trampoline as ffi::ProgressCallbackFn)
+        } else {
+            None
+        };
+
+        unsafe {
+            let result = ffi::sevenzip_extract_with_metadata(
+                c_archive.as_ptr(),
+                c_output.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                metadata.preserve_permissions as i32,
+                metadata.preserve_timestamps as i32,
+                callback,
+                user_data,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(header_error(result, password));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract only the named entries from an archive
+    pub fn extract_files(
+        &self,
+        archive_path: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        names: &[impl AsRef<str>],
+        password: Option<&str>,
+    ) -> Result<()> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+        let c_output = CString::new(path_to_str(output_dir.as_ref())?)?;
+        let c_password = password.map(CString::new).transpose()?;
+        let c_names: Result<Vec<CString>> = names
+            .iter()
+            .map(|n| Ok(CString::new(n.as_ref())?))
+            .collect();
+        let c_names = c_names?;
+        let name_ptrs = c_string_ptrs(&c_names);
+
+        unsafe {
+            let result = ffi::sevenzip_extract_files(
+                c_archive.as_ptr(),
+                c_output.as_ptr(),
+                name_ptrs.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                None,
+                std::ptr::null_mut(),
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(header_error(result, password));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract a single named entry directly into memory, without writing
+    /// plaintext to disk
+    ///
+    /// Useful for forensic or streaming callers where even a temporary
+    /// decrypted file on disk is undesirable.
+    pub fn extract_to_memory(
+        &self,
+        archive_path: impl AsRef<Path>,
+        entry_name: &str,
+        password: Option<&str>,
+    ) -> Result<Vec<u8>> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+        let c_entry = CString::new(entry_name)?;
+        let c_password = password.map(CString::new).transpose()?;
+        let mut buf: Vec<u8> = Vec::new();
+
+        unsafe {
+            let result = ffi::sevenzip_extract_entry_to_memory(
+                c_archive.as_ptr(),
+                c_entry.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                extract_data_trampoline,
+                &mut buf as *mut Vec<u8> as *mut c_void,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(header_error(result, password));
+            }
+        }
+        Ok(buf)
+    }
+
+    /// Extract a single named entry to `dest_path`, creating `dest_path`'s
+    /// parent directories first
+    ///
+    /// Built on [`SevenZip::extract_to_memory`], so it shares the same
+    /// "decode the whole entry, then write it" behavior; unlike
+    /// [`SevenZip::extract`], which lets the native extractor lay out
+    /// `output_dir` itself, this is for a caller who already knows exactly
+    /// where one entry should land (e.g. renaming it, or rebuilding a tree
+    /// one entry at a time). `create_dir_all` on the parent is idempotent,
+    /// so a parent created by an earlier call (or already present) is not
+    /// an error.
+    pub fn extract_entry_to_path(
+        &self,
+        archive_path: impl AsRef<Path>,
+        entry_name: &str,
+        dest_path: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let dest_path = dest_path.as_ref();
+        let data = self.extract_to_memory(archive_path, entry_name, password)?;
+        if let Some(parent) = dest_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(dest_path, &data)?;
+        Ok(())
+    }
+
+    /// Extract every file entry in an archive into memory
+    ///
+    /// Built on [`SevenZip::list`] and [`SevenZip::extract_to_memory`];
+    /// directory entries are skipped since they carry no content.
+    pub fn extract_all_to_memory(
+        &self,
+        archive_path: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let archive_path = archive_path.as_ref();
+        self.list(archive_path, password)?
+            .into_iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| {
+                let data = self.extract_to_memory(archive_path, &entry.name, password)?;
+                Ok((entry.name, data))
+            })
+            .collect()
+    }
+
+    /// Decode a single entry directly into `writer`, without writing
+    /// plaintext to disk or buffering the whole entry as a returned `Vec`
+    ///
+    /// A thin convenience over [`SevenZip::extract_to_memory`] for callers
+    /// that already have a `Write` destination (a socket, an in-progress
+    /// buffer) and don't need the intermediate `Vec<u8>`.
+    pub fn extract_entry_to_writer(
+        &self,
+        archive_path: impl AsRef<Path>,
+        entry_name: &str,
+        password: Option<&str>,
+        mut writer: impl Write,
+    ) -> Result<()> {
+        let data = self.extract_to_memory(archive_path, entry_name, password)?;
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// List the contents of an archive, optionally password-protected
+    ///
+    /// The password, if any, is passed through to the native header-open
+    /// path, so this also enumerates archives with encrypted headers (where
+    /// even filenames and sizes aren't readable without it): a missing
+    /// password returns [`Error::PasswordRequired`], and a supplied-but-wrong
+    /// one returns [`Error::WrongPassword`]. [`SevenZip::list_with_password`]
+    /// is a same-named-as-`extract_with_password` alias for this when the
+    /// archive is known in advance to need one.
+    ///
+    /// Built on [`SevenZip::list_iter`], so the whole entry table is still
+    /// materialized as a `Vec` here; prefer `list_iter` directly for an
+    /// archive large enough that holding every entry in memory at once matters.
+    pub fn list(&self, archive_path: impl AsRef<Path>, password: Option<&str>) -> Result<Vec<ArchiveEntry>> {
+        self.list_iter(archive_path, password)?.collect()
+    }
+
+    /// List the contents of a password-protected archive
+    ///
+    /// Equivalent to `list(archive_path, Some(password))`; provided for
+    /// parity with [`SevenZip::extract_with_password`] so callers that
+    /// already know an archive needs a password (including one with
+    /// encrypted headers) don't need to wrap it in `Some` themselves.
+    pub fn list_with_password(
+        &self,
+        archive_path: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<Vec<ArchiveEntry>> {
+        self.list(archive_path, Some(password))
+    }
+
+    /// List the contents of an archive one entry at a time
+    ///
+    /// Prefer this over [`SevenZip::list`] for very large archives: entries
+    /// are pulled from the native reader lazily, so iteration can stop (or
+    /// the returned [`ListIter`] can simply be dropped) without parsing the
+    /// rest of the header.
+    pub fn list_iter(&self, archive_path: impl AsRef<Path>, password: Option<&str>) -> Result<ListIter> {
+        ListIter::open(archive_path.as_ref(), password)
+    }
+
+    /// List the contents of an archive one entry at a time
+    ///
+    /// An alias for [`SevenZip::list_iter`] under the name more directly
+    /// matching "streaming entry iterator": each [`ArchiveEntry`] is parsed
+    /// from the native reader's central directory lazily, one per `next()`
+    /// call, rather than collected into a `Vec` up front like
+    /// [`SevenZip::list`]. One caveat on individual-entry errors: since the
+    /// native reader's position after a failed `next()` call isn't
+    /// well-defined, this iterator (like [`SevenZip::list_iter`]) ends after
+    /// surfacing the first one as `Some(Err(_))`, rather than skipping past
+    /// it to keep walking remaining entries.
+    pub fn iter_entries(&self, archive_path: impl AsRef<Path>, password: Option<&str>) -> Result<ListIter> {
+        self.list_iter(archive_path, password)
+    }
+
+    /// List the contents of an archive one entry at a time, as an opaque
+    /// `impl Iterator` rather than the concrete [`ListIter`] type
+    ///
+    /// Same underlying streaming listing as [`SevenZip::list_iter`] (entries
+    /// are parsed from the central directory lazily, not collected into a
+    /// `Vec` up front like [`SevenZip::list`]), so a huge multi-volume
+    /// archive can be filtered, `take`n from, or aborted early without
+    /// paying to materialize or hold the whole entry table in memory.
+    pub fn list_archive(
+        &self,
+        archive_path: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<impl Iterator<Item = Result<ArchiveEntry>>> {
+        self.list_iter(archive_path, password)
+    }
+
+    /// Verify the integrity of an archive without extracting it
+    pub fn test_archive(&self, archive_path: impl AsRef<Path>, password: Option<&str>) -> Result<()> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+        let c_password = password.map(CString::new).transpose()?;
+
+        unsafe {
+            let result = ffi::sevenzip_test_archive(
+                c_archive.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                None,
+                std::ptr::null_mut(),
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(header_error(result, password));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify archive integrity, optionally recomputing and checking every
+    /// entry's CRC32 against the archive header
+    ///
+    /// With `verify_crc` set, this is strictly stronger than
+    /// [`SevenZip::test_archive`]: each file entry is additionally decoded
+    /// into memory via [`SevenZip::extract_to_memory`] and its content
+    /// checksummed, returning [`Error::ChecksumMismatch`] on the first entry
+    /// whose recomputed CRC32 diverges from the one recorded at `list` time.
+    /// Entries with no recorded CRC32 (see [`ArchiveEntry::crc32`]) are
+    /// skipped, since there is nothing to compare against.
+    pub fn test_archive_with_crc(
+        &self,
+        archive_path: impl AsRef<Path>,
+        password: Option<&str>,
+        verify_crc: bool,
+    ) -> Result<()> {
+        let archive_path = archive_path.as_ref();
+        self.test_archive(archive_path, password)?;
+
+        if !verify_crc {
+            return Ok(());
+        }
+
+        for entry in self.list(archive_path, password)? {
+            if entry.is_dir {
+                continue;
+            }
+            let expected = match entry.crc32 {
+                Some(crc) => crc,
+                None => continue,
+            };
+            let data = self.extract_to_memory(archive_path, &entry.name, password)?;
+            let actual = crc32_ieee(&data);
+            if actual != expected {
+                return Err(Error::ChecksumMismatch { name: entry.name, expected, actual });
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify every entry's CRC32 without extracting anything to disk,
+    /// reporting a pass/fail result per entry rather than stopping at the
+    /// first mismatch
+    ///
+    /// Unlike [`SevenZip::test_archive_with_crc`], which treats any mismatch
+    /// as a hard error, this is meant for callers that want a full integrity
+    /// report (e.g. `7zz t`-style output) in one pass. Entries with no
+    /// recorded CRC32 report `true`, since there is nothing to compare
+    /// against; directory entries are omitted entirely.
+    pub fn test_integrity(
+        &self,
+        archive_path: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<Vec<(String, bool)>> {
+        let archive_path = archive_path.as_ref();
+        self.test_archive(archive_path, password)?;
+
+        self.list(archive_path, password)?
+            .into_iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| {
+                let ok = match entry.crc32 {
+                    Some(expected) => {
+                        let data = self.extract_to_memory(archive_path, &entry.name, password)?;
+                        crc32_ieee(&data) == expected
+                    }
+                    None => true,
+                };
+                Ok((entry.name, ok))
+            })
+            .collect()
+    }
+
+    /// Alias for [`SevenZip::test_integrity`], matching the `zip` crate's
+    /// naming for the same operation: in-process `7z t`-equivalent
+    /// integrity testing without extracting anything to disk
+    pub fn verify(
+        &self,
+        archive_path: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<Vec<(String, bool)>> {
+        self.test_integrity(archive_path, password)
+    }
+
+    /// Extract an archive to `output_dir`, then verify each written file's
+    /// CRC32 against the archive header
+    ///
+    /// Builds on [`SevenZip::extract_with_password`] and
+    /// [`SevenZip::test_integrity`]'s pass/fail reporting, but checks the
+    /// files actually written to disk rather than re-decoding the archive
+    /// stream a second time.
+    pub fn extract_verified(
+        &self,
+        archive_path: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<Vec<(String, bool)>> {
+        let archive_path = archive_path.as_ref();
+        let output_dir = output_dir.as_ref();
+        self.extract_with_password(archive_path, output_dir, password, None)?;
+
+        self.list(archive_path, password)?
+            .into_iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| {
+                let ok = match entry.crc32 {
+                    Some(expected) => {
+                        let data = std::fs::read(output_dir.join(&entry.name))?;
+                        crc32_ieee(&data) == expected
+                    }
+                    None => true,
+                };
+                Ok((entry.name, ok))
+            })
+            .collect()
+    }
+
+    /// Create an archive using the streaming/multi-volume code path
+    ///
+    /// When `options.split_size` is non-zero and the total input size exceeds
+    /// it, the archive is written as `archive_path.001`, `archive_path.002`, etc.
+    pub fn create_archive_streaming(
+        &self,
+        archive_path: impl AsRef<Path>,
+        input_paths: &[impl AsRef<Path>],
+        level: CompressionLevel,
+        options: Option<&StreamOptions>,
+        mut progress: Option<StreamProgressCallback>,
+    ) -> Result<()> {
+        let c_archive = CString::new(path_to_str(archive_path.as_ref())?)?;
+
+        let default_options = StreamOptions::default();
+        let options = options.unwrap_or(&default_options);
+
+        for input_path in input_paths {
+            if let Some(cb) = progress.as_mut() {
+                cb(ProgressEvent::FileStarted {
+                    path: input_path.as_ref().to_path_buf(),
+                });
+            }
+        }
+
+        // When dedup is on, archive a staged directory of deduplicated
+        // chunks (plus a reassembly manifest) instead of `input_paths`
+        // directly; the staging dir is removed once archiving is done,
+        // whether or not it succeeded.
+        let dedup_temp_dir = if options.dedup {
+            Some(stage_dedup_temp_dir(input_paths)?)
+        } else {
+            None
+        };
+
+        // `per_file` can't give each input its own codec (the FFI call takes
+        // one method for the whole archive), so it's approximated: only
+        // override to `Copy` when *every* input looks incompressible.
+        let method = if options.per_file
+            && !input_paths.is_empty()
+            && input_paths
+                .iter()
+                .all(|p| looks_incompressible(p.as_ref()).unwrap_or(false))
+        {
+            CompressionMethod::Copy
+        } else {
+            options.method
+        };
+
+        let result = (|| -> Result<()> {
+            let c_paths = match &dedup_temp_dir {
+                Some(dir) => paths_to_c_strings(std::slice::from_ref(dir))?,
+                None => paths_to_c_strings(input_paths)?,
+            };
+            let path_ptrs = c_string_ptrs(&c_paths);
+
+            let (_c_password, c_options) = Self::compress_options(
+                options.password.as_deref(),
+                options.num_threads,
+                method,
+                options.ppmd,
+                options.bzip2,
+                options.lzma2,
+                options.encrypt_headers,
+                options.metadata,
+            )?;
+
+            let user_data = progress
+                .as_mut()
+                .map_or(std::ptr::null_mut(), |cb| cb as *mut StreamProgressCallback as *mut c_void);
+            let progress_cb = if user_data.is_null() {
+                None
+            } else {
+                Some(stream_progress_trampoline as ffi::ProgressCallbackFn)
+            };
+
+            unsafe {
+                let result = ffi::sevenzip_create_multivolume_7z(
+                    c_archive.as_ptr(),
+                    path_ptrs.as_ptr(),
+                    level.into(),
+                    options.split_size,
+                    &c_options,
+                    progress_cb,
+                    user_data,
+                );
+                if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                    return Err(Error::from_code(result));
+                }
+            }
+            Ok(())
+        })();
+
+        if let Some(dir) = dedup_temp_dir {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+
+        if result.is_ok() {
+            if let Some(cb) = progress.as_mut() {
+                for (i, path) in crate::advanced::volume_sizes(archive_path.as_ref(), options.split_size)
+                    .into_iter()
+                    .enumerate()
+                {
+                    let index = (i + 1) as u32;
+                    cb(ProgressEvent::VolumeStarted {
+                        index,
+                        path: path.clone(),
+                    });
+                    let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                    cb(ProgressEvent::VolumeFinished { index, path, size });
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Extract an archive created by [`SevenZip::create_archive_streaming`]
+    /// with [`StreamOptions::dedup`] set, reassembling the original input
+    /// files from the staged chunk pool rather than leaving the caller with
+    /// a directory of renamed chunks and a manifest
+    ///
+    /// Extracts the archive to a private temp directory via
+    /// [`SevenZip::extract_secure`] (its staged `chunk_xxx.bin`/
+    /// `manifest.txt` entry names are just as untrusted as any other
+    /// archive's), finds the `manifest.txt` [`stage_dedup_temp_dir`] wrote
+    /// into it, then for each recorded file reassembles its content via
+    /// [`dedup::reassemble_file`] and writes it under `output_dir`,
+    /// sanitized the same way `extract_secure` sanitizes ordinary entry
+    /// names (an original input path with a root or `..` component fails
+    /// the whole call with [`Error::UnsafeArchive`] rather than escaping
+    /// `output_dir`). Fails with [`Error::InvalidArchive`] if no
+    /// `manifest.txt` is found, i.e. the archive wasn't created with
+    /// `dedup` set.
+    pub fn extract_streaming_dedup(
+        &self,
+        archive_path: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let output_dir = output_dir.as_ref();
+        let staging_dir = unique_dedup_temp_dir();
+        std::fs::create_dir_all(&staging_dir)?;
+
+        let result = (|| -> Result<()> {
+            // `extract_secure`, not `extract_with_password` - the staged
+            // archive's own `chunk_xxx.bin`/`manifest.txt` entry names are
+            // just as untrusted as any other archive's, and need the same
+            // Zip-Slip sanitization before a single byte lands in
+            // `staging_dir`, even though the reassembled *output* names get
+            // `sanitized_entry_path`'d separately below.
+            self.extract_secure(archive_path, &staging_dir, password, &ExtractOptions::default())?;
+
+            let manifest_path = find_file_named(&staging_dir, "manifest.txt").ok_or_else(|| {
+                Error::InvalidArchive(
+                    "no manifest.txt found; archive was not created with StreamOptions::dedup set".to_string(),
+                )
+            })?;
+            let chunk_dir = manifest_path
+                .parent()
+                .expect("manifest.txt always has a parent directory")
+                .to_path_buf();
+            let manifest_text = std::fs::read_to_string(&manifest_path)?;
+
+            for entry in dedup::parse_manifest(&manifest_text)? {
+                let name = entry.path.to_string_lossy().into_owned();
+                let dest = sanitized_entry_path(output_dir, None, 0, &name)?
+                    .ok_or_else(|| Error::InvalidArchive(format!("manifest entry '{}' has an empty path", name)))?;
+                let data = dedup::reassemble_file(&entry, &chunk_dir)?;
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&dest, &data)?;
+            }
+            Ok(())
+        })();
+
+        let _ = std::fs::remove_dir_all(&staging_dir);
+        result
+    }
+
+    /// Compress `input_path` to a single raw compressed stream at
+    /// `output_path` - not a `.7z` container, just the codec's own framing
+    /// (e.g. `.lzma`/`.xz`), the way `gzip`/`xz`/`zstd` single-file tools do
+    ///
+    /// A thin, method-form entry point over [`crate::advanced::compress_lzma`]
+    /// / [`crate::advanced::compress_lzma2`], picked by `codec`; see
+    /// [`SevenZip::decompress_file`] for the inverse.
+    pub fn compress_file(
+        &self,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+        level: CompressionLevel,
+        codec: StreamCodec,
+    ) -> Result<()> {
+        match codec {
+            StreamCodec::Lzma => crate::advanced::compress_lzma(input_path, output_path, level),
+            StreamCodec::Lzma2 => crate::advanced::compress_lzma2(input_path, output_path, level),
+        }
+    }
+
+    /// Decompress a single raw compressed stream produced by
+    /// [`SevenZip::compress_file`] with the same `codec`
+    ///
+    /// Returns whatever typed [`Error`] the underlying codec call returns
+    /// (e.g. [`Error::InvalidArchive`]) if `input_path` isn't a stream that
+    /// codec recognizes; nothing is written to `output_path` in that case.
+    pub fn decompress_file(
+        &self,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+        codec: StreamCodec,
+    ) -> Result<()> {
+        match codec {
+            StreamCodec::Lzma => crate::advanced::decompress_lzma(input_path, output_path),
+            StreamCodec::Lzma2 => crate::advanced::decompress_lzma2(input_path, output_path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_codec_default_is_lzma2() {
+        assert_eq!(StreamCodec::default(), StreamCodec::Lzma2);
+    }
+
+    #[test]
+    fn test_header_error_distinguishes_missing_from_wrong_password() {
+        let missing = header_error(ffi::SevenZipErrorCode::SEVENZIP_ERROR_PASSWORD_REQUIRED, None);
+        let wrong = header_error(ffi::SevenZipErrorCode::SEVENZIP_ERROR_PASSWORD_REQUIRED, Some("guess"));
+        assert_ne!(missing.to_string(), wrong.to_string());
+        assert!(matches!(missing, Error::PasswordRequired(_)));
+        assert!(matches!(wrong, Error::WrongPassword(_)));
+    }
+
+    #[test]
+    fn test_header_error_passes_through_other_codes() {
+        let err = header_error(ffi::SevenZipErrorCode::SEVENZIP_ERROR_INVALID_ARCHIVE, None);
+        assert!(matches!(err, Error::InvalidArchive(_)));
+    }
+
+    #[test]
+    fn test_header_error_treats_extract_failure_with_password_as_wrong_password() {
+        let with_password = header_error(ffi::SevenZipErrorCode::SEVENZIP_ERROR_EXTRACT, Some("guess"));
+        assert!(matches!(with_password, Error::WrongPassword(_)));
+
+        let without_password = header_error(ffi::SevenZipErrorCode::SEVENZIP_ERROR_EXTRACT, None);
+        assert!(matches!(without_password, Error::Extract(_)));
+    }
+
+    #[test]
+    fn test_extract_options_default_is_unlimited() {
+        let opts = ExtractOptions::default();
+        assert_eq!(opts.max_total_size, 0);
+        assert_eq!(opts.max_entries, 0);
+        assert_eq!(opts.strip_components, 0);
+        assert!(opts.dest_prefix.is_none());
+        assert!(!opts.verify_crc);
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_rejects_parent_dir_traversal() {
+        let out = Path::new("/tmp/out");
+        let result = sanitized_entry_path(out, None, 0, "../../etc/cron.d/x");
+        assert!(matches!(result, Err(Error::UnsafeArchive(_))));
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_rejects_absolute_path() {
+        let out = Path::new("/tmp/out");
+        let result = sanitized_entry_path(out, None, 0, "/etc/passwd");
+        assert!(matches!(result, Err(Error::UnsafeArchive(_))));
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_accepts_normal_relative_path() {
+        let out = Path::new("/tmp/out");
+        let result = sanitized_entry_path(out, None, 0, "docs/readme.txt")
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, Path::new("/tmp/out/docs/readme.txt"));
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_strips_leading_components() {
+        let out = Path::new("/tmp/out");
+        let result = sanitized_entry_path(out, None, 1, "wrapper/docs/readme.txt")
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, Path::new("/tmp/out/docs/readme.txt"));
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_skips_fully_stripped_entry() {
+        let out = Path::new("/tmp/out");
+        let result = sanitized_entry_path(out, None, 2, "wrapper").unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_applies_dest_prefix() {
+        let out = Path::new("/tmp/out");
+        let prefix = Path::new("extracted");
+        let result = sanitized_entry_path(out, Some(prefix), 0, "a.txt")
+            .unwrap()
+            .unwrap();
+        assert_eq!(result, Path::new("/tmp/out/extracted/a.txt"));
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_rejects_unsafe_dest_prefix() {
+        let out = Path::new("/tmp/out");
+        let prefix = Path::new("../escape");
+        let result = sanitized_entry_path(out, Some(prefix), 0, "a.txt");
+        assert!(matches!(result, Err(Error::UnsafeArchive(_))));
+    }
+
+    #[test]
+    fn test_sanitized_entry_path_strip_then_parent_dir_is_still_rejected() {
+        let out = Path::new("/tmp/out");
+        let result = sanitized_entry_path(out, None, 1, "safe/../../etc/passwd");
+        assert!(matches!(result, Err(Error::UnsafeArchive(_))));
+    }
+
+    #[test]
+    fn test_extract_entry_to_path_fails_on_missing_archive() {
+        let sz = SevenZip::new().unwrap();
+        let dest = unique_dedup_temp_dir().with_extension("nested/dir/out.bin");
+        let result = sz.extract_entry_to_path("/nonexistent.7z", "a.txt", &dest, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_extract_with_password_fails_on_missing_archive() {
+        let sz = SevenZip::new().unwrap();
+        let out = unique_dedup_temp_dir();
+        let result = sz.extract_with_password("/nonexistent.7z", &out, Some("hunter2"), None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_iter_entries_fails_on_missing_archive() {
+        let sz = SevenZip::new().unwrap();
+        let result = sz.iter_entries("/nonexistent.7z", None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_compression_level_conversion() {
+        assert_eq!(i32::from(CompressionLevel::Store), 0);
+        assert_eq!(i32::from(CompressionLevel::Ultra), 9);
+    }
+
+    #[test]
+    fn test_crc32_known_vector() {
+        // "123456789" is the standard CRC32/IEEE check value
+        assert_eq!(crc32_ieee(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_compression_ratio() {
+        let entry = ArchiveEntry {
+            name: "file.txt".to_string(),
+            size: 1000,
+            packed_size: 250,
+            is_dir: false,
+            crc32: None,
+            method: Some(CompressionMethod::Lzma2),
+        };
+        assert_eq!(entry.compression_ratio(), 75.0);
+    }
+
+    #[test]
+    fn test_compression_method_i32_roundtrip() {
+        for method in [
+            CompressionMethod::Lzma2,
+            CompressionMethod::Lzma,
+            CompressionMethod::Bzip2,
+            CompressionMethod::Ppmd,
+            CompressionMethod::Deflate,
+            CompressionMethod::Copy,
+        ] {
+            assert_eq!(CompressionMethod::from_i32(method.into()), Some(method));
+        }
+        assert_eq!(CompressionMethod::from_i32(-1), None);
+    }
+
+    #[test]
+    fn test_stream_options_default() {
+        let opts = StreamOptions::default();
+        assert_eq!(opts.split_size, 0);
+        assert!(opts.password.is_none());
+        assert_eq!(opts.method, CompressionMethod::Lzma2);
+        assert!(!opts.dedup);
+        assert!(opts.lzma2.is_none());
+        assert!(!opts.per_file);
+    }
+
+    #[test]
+    fn test_lzma2_options_default_dict_size_is_zero() {
+        let opts = Lzma2Options::default();
+        assert_eq!(opts.dict_size, 0);
+    }
+
+    #[test]
+    fn test_looks_incompressible_detects_random_like_data() {
+        let path = unique_dedup_temp_dir().with_extension("random.bin");
+        let data: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) % 256) as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+        assert!(looks_incompressible(&path).unwrap());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_looks_incompressible_rejects_repetitive_data() {
+        let path = unique_dedup_temp_dir().with_extension("repetitive.bin");
+        std::fs::write(&path, vec![0x41u8; 4096]).unwrap();
+        assert!(!looks_incompressible(&path).unwrap());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_progress_event_bytes_processed_fields() {
+        let event = ProgressEvent::BytesProcessed {
+            total_in: 10,
+            total_out: 100,
+        };
+        match event {
+            ProgressEvent::BytesProcessed { total_in, total_out } => {
+                assert_eq!(total_in, 10);
+                assert_eq!(total_out, 100);
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
+    #[test]
+    fn test_stage_dedup_temp_dir_rejects_directories() {
+        let dir = std::env::temp_dir();
+        let result = stage_dedup_temp_dir(&[&dir]);
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_stage_dedup_temp_dir_produces_a_reassemblable_pool() {
+        let content: Vec<u8> = (0..50_000u32).map(|i| (i % 251) as u8).collect();
+        let input = unique_dedup_temp_dir().with_extension("in.bin");
+        std::fs::write(&input, &content).unwrap();
+
+        let staged = stage_dedup_temp_dir(&[&input]).unwrap();
+        let manifest_path = find_file_named(&staged, "manifest.txt").unwrap();
+        let manifest_text = std::fs::read_to_string(&manifest_path).unwrap();
+        let parsed = dedup::parse_manifest(&manifest_text).unwrap();
+        let reassembled = dedup::reassemble_file(&parsed[0], &staged).unwrap();
+
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_dir_all(&staged);
+
+        assert_eq!(reassembled, content);
+    }
+
+    #[test]
+    fn test_find_file_named_searches_recursively() {
+        let dir = unique_dedup_temp_dir();
+        let nested = dir.join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("target.txt"), b"hi").unwrap();
+
+        let found = find_file_named(&dir, "target.txt");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(found, Some(nested.join("target.txt")));
+    }
+
+    #[test]
+    fn test_extract_streaming_dedup_fails_on_missing_archive() {
+        let sz = SevenZip::new().unwrap();
+        let out = unique_dedup_temp_dir();
+        let result = sz.extract_streaming_dedup("/nonexistent.7z", &out, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_compression_method_conversion() {
+        assert_eq!(i32::from(CompressionMethod::Lzma2), 0);
+        assert_eq!(i32::from(CompressionMethod::Copy), 5);
+    }
+
+    #[test]
+    fn test_metadata_options_default_preserves_everything() {
+        let opts = MetadataOptions::default();
+        assert!(opts.store_symlinks);
+        assert!(opts.preserve_permissions);
+        assert!(opts.preserve_timestamps);
+    }
+
+    #[test]
+    fn test_encrypt_headers_requires_password() {
+        let mut opts = StreamOptions::default();
+        opts.encrypt_headers = true;
+        let sz = SevenZip::new().unwrap();
+        let result = sz.create_archive_streaming(
+            "unused.7z",
+            &["unused_input.txt"],
+            CompressionLevel::Normal,
+            Some(&opts),
+            None,
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_stream_options_per_method_tuning() {
+        let mut opts = StreamOptions::default();
+        opts.method = CompressionMethod::Ppmd;
+        opts.ppmd = Some(PpmdOptions { order: 12, mem_mb: 256 });
+        assert_eq!(opts.ppmd.unwrap().order, 12);
+
+        opts.method = CompressionMethod::Bzip2;
+        opts.bzip2 = Some(Bzip2Options { block_size: 5 });
+        assert_eq!(opts.bzip2.unwrap().block_size, 5);
+    }
+}
@@ -0,0 +1,479 @@
+//! Content-defined chunking and cross-file deduplication
+//!
+//! [`dedup_files`] splits a set of in-memory files into variable-length
+//! chunks using a FastCDC-style rolling gear hash, so that a byte insertion
+//! or deletion inside one file shifts only the chunks around the edit
+//! instead of reshuffling every fixed-size block after it. Chunks with
+//! identical content (whether inside one file or shared across several) are
+//! stored only once; each file's [`FileManifest`] records the chunk
+//! sequence needed to reassemble it.
+
+use crate::error::{Error, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Deterministic SplitMix64 step, used only to fill [`GEAR`] at compile time
+const fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+const fn build_gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = splitmix64(i as u64 + 1);
+        i += 1;
+    }
+    table
+}
+
+/// Per-byte constants for the rolling gear fingerprint used by [`cut_point`]
+///
+/// Generated from a fixed SplitMix64 seed rather than loaded from an RNG, so
+/// chunk boundaries (and therefore dedup results) are reproducible across
+/// runs and platforms.
+const GEAR: [u64; 256] = build_gear_table();
+
+/// Chunk-size bounds and normalized-chunking tuning for [`dedup_files`]
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkerConfig {
+    /// No chunk is cut shorter than this, even if the fingerprint matches
+    pub min_size: usize,
+    /// Target average chunk size; the cut mask tightens/loosens around it
+    pub avg_size: usize,
+    /// A chunk is force-cut at this length if no fingerprint match occurs first
+    pub max_size: usize,
+}
+
+impl Default for ChunkerConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 4 * 1024,
+            avg_size: 16 * 1024,
+            max_size: 64 * 1024,
+        }
+    }
+}
+
+impl ChunkerConfig {
+    /// Normalized-chunking mask pair: `mask_s` (more one-bits, harder to
+    /// satisfy) is used before `avg_size` to discourage premature cuts,
+    /// `mask_l` (fewer one-bits, easier to satisfy) is used after it so the
+    /// cut converges near the target instead of drifting out to `max_size`
+    fn masks(&self) -> (u64, u64) {
+        let bits = usize::BITS - self.avg_size.max(2).leading_zeros() - 1;
+        let mask_s = (1u64 << (bits + 1).min(63)) - 1;
+        let mask_l = (1u64 << bits.saturating_sub(1).max(1)) - 1;
+        (mask_s, mask_l)
+    }
+}
+
+/// Find the end offset (exclusive) of the first content-defined chunk in
+/// `data`, per `config`'s size bounds
+///
+/// Skips `min_size` bytes unconditionally, then rolls a gear fingerprint
+/// (`fp = (fp << 1) + GEAR[byte]`) byte by byte, cutting as soon as
+/// `fp & mask == 0` for the mask appropriate to how far past `min_size` the
+/// scan has gone. If no fingerprint match occurs before `max_size` (or the
+/// end of `data`), the chunk is force-cut there.
+fn cut_point(data: &[u8], config: &ChunkerConfig) -> usize {
+    let len = data.len();
+    if len <= config.min_size {
+        return len;
+    }
+
+    let (mask_s, mask_l) = config.masks();
+    let limit = config.max_size.min(len);
+    let mut fp: u64 = 0;
+    let mut i = config.min_size;
+    while i < limit {
+        fp = (fp << 1).wrapping_add(GEAR[data[i] as usize]);
+        let mask = if i < config.avg_size { mask_s } else { mask_l };
+        if fp & mask == 0 {
+            return i + 1;
+        }
+        i += 1;
+    }
+    limit
+}
+
+/// SHA-256 of a chunk's content (32 bytes = 256 bits), used as its dedup key
+fn hash_chunk(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// One deduplicated chunk: its content hash and the bytes themselves
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    /// SHA-256 of `data`
+    pub hash: [u8; 32],
+    /// The chunk's content
+    pub data: Vec<u8>,
+}
+
+/// Which chunks (by index into [`DedupResult::unique_chunks`]) make up one
+/// original input file, in order
+#[derive(Debug, Clone)]
+pub struct FileManifest {
+    /// The file's original path, as passed to [`dedup_files`]
+    pub path: PathBuf,
+    /// The file's original size in bytes
+    pub size: u64,
+    /// Ordered chunk indices reconstructing the file's content
+    pub chunk_indices: Vec<usize>,
+}
+
+/// Output of [`dedup_files`]: the pool of unique chunks plus one manifest
+/// per input file describing how to reassemble it from that pool
+pub struct DedupResult {
+    /// Every distinct chunk seen across all input files, in first-seen order
+    pub unique_chunks: Vec<Chunk>,
+    /// One entry per input file, in the order `files` was given to [`dedup_files`]
+    pub files: Vec<FileManifest>,
+}
+
+impl DedupResult {
+    /// Render a plain-text manifest: one line per file, giving its original
+    /// path, byte size, and the hex-encoded hashes of the chunks (in order)
+    /// it's made of, so a later pass can reassemble it from
+    /// [`Self::unique_chunks`]
+    pub fn manifest_text(&self) -> String {
+        let mut out = String::new();
+        for file in &self.files {
+            out.push_str(&file.path.to_string_lossy());
+            out.push('\t');
+            out.push_str(&file.size.to_string());
+            for &index in &file.chunk_indices {
+                out.push('\t');
+                for byte in &self.unique_chunks[index].hash {
+                    out.push_str(&format!("{:02x}", byte));
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Split `files` into content-defined chunks and deduplicate identical
+/// chunks across all of them
+///
+/// `files` is a list of `(original_path, content)` pairs; `config` tunes the
+/// chunk-size bounds. Chunk boundaries depend only on content, so the same
+/// bytes appearing in two different files (or twice in one file) land in
+/// the same chunk and are stored only once in [`DedupResult::unique_chunks`].
+pub fn dedup_files(files: &[(PathBuf, Vec<u8>)], config: &ChunkerConfig) -> DedupResult {
+    let mut index_of: HashMap<[u8; 32], usize> = HashMap::new();
+    let mut unique_chunks = Vec::new();
+    let mut file_manifests = Vec::with_capacity(files.len());
+
+    for (path, data) in files {
+        let mut offset = 0;
+        let mut chunk_indices = Vec::new();
+        while offset < data.len() {
+            let len = cut_point(&data[offset..], config);
+            let chunk_data = &data[offset..offset + len];
+            let hash = hash_chunk(chunk_data);
+            let index = *index_of.entry(hash).or_insert_with(|| {
+                unique_chunks.push(Chunk {
+                    hash,
+                    data: chunk_data.to_vec(),
+                });
+                unique_chunks.len() - 1
+            });
+            chunk_indices.push(index);
+            offset += len;
+        }
+        file_manifests.push(FileManifest {
+            path: path.clone(),
+            size: data.len() as u64,
+            chunk_indices,
+        });
+    }
+
+    DedupResult {
+        unique_chunks,
+        files: file_manifests,
+    }
+}
+
+/// One file's manifest line, parsed back out of [`DedupResult::manifest_text`]
+///
+/// Unlike [`FileManifest`], which references chunks by index into an
+/// in-memory [`DedupResult::unique_chunks`], this references them by their
+/// hash directly, since a parsed manifest has no such in-memory pool to
+/// index into - the hashes are how [`reassemble_file`] finds each chunk on
+/// disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// The file's original path, as recorded by [`DedupResult::manifest_text`]
+    pub path: PathBuf,
+    /// The file's original size in bytes
+    pub size: u64,
+    /// Ordered chunk hashes reconstructing the file's content
+    pub chunk_hashes: Vec<[u8; 32]>,
+}
+
+fn parse_hash(hex: &str) -> Result<[u8; 32]> {
+    if hex.len() != 64 {
+        return Err(Error::InvalidArchive(format!(
+            "manifest chunk hash has {} hex characters, expected 64",
+            hex.len()
+        )));
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|_| Error::InvalidArchive(format!("manifest chunk hash is not valid hex: {}", hex)))?;
+    }
+    Ok(hash)
+}
+
+/// Parse the manifest text produced by [`DedupResult::manifest_text`] back
+/// into one [`ManifestEntry`] per file, in the same order it was written
+pub fn parse_manifest(text: &str) -> Result<Vec<ManifestEntry>> {
+    text.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut fields = line.split('\t');
+            let path = fields
+                .next()
+                .ok_or_else(|| Error::InvalidArchive("manifest line missing path field".to_string()))?;
+            let size: u64 = fields
+                .next()
+                .ok_or_else(|| Error::InvalidArchive("manifest line missing size field".to_string()))?
+                .parse()
+                .map_err(|_| Error::InvalidArchive("manifest size field is not a valid integer".to_string()))?;
+            let chunk_hashes = fields.map(parse_hash).collect::<Result<Vec<_>>>()?;
+            Ok(ManifestEntry { path: PathBuf::from(path), size, chunk_hashes })
+        })
+        .collect()
+}
+
+/// The on-disk filename a chunk pool stores a chunk's content under, keyed
+/// by its content hash so a parsed [`ManifestEntry`] can look it up without
+/// needing the original in-memory index-to-hash mapping
+pub fn chunk_file_name(hash: &[u8; 32]) -> String {
+    let mut name = String::with_capacity(64 + 4);
+    for byte in hash {
+        name.push_str(&format!("{:02x}", byte));
+    }
+    name.push_str(".bin");
+    name
+}
+
+/// Reassemble one file's content from a chunk pool directory, given its
+/// parsed [`ManifestEntry`]
+///
+/// Each chunk is looked up in `chunk_dir` by [`chunk_file_name`] and
+/// concatenated in manifest order; the result is checked against
+/// `entry.size` so a missing or truncated chunk is caught before the caller
+/// trusts the reassembled bytes.
+pub fn reassemble_file(entry: &ManifestEntry, chunk_dir: &std::path::Path) -> Result<Vec<u8>> {
+    // Not `Vec::with_capacity(entry.size as usize)` - `entry.size` comes
+    // straight from a parsed manifest, which for
+    // `SevenZip::extract_streaming_dedup` is itself untrusted archive
+    // content. Growing the buffer as real chunk bytes actually arrive means
+    // a manifest line that lies about a huge size fails on the length check
+    // below instead of aborting the process with an allocation failure
+    // before a single byte is read.
+    let mut data = Vec::new();
+    for hash in &entry.chunk_hashes {
+        let chunk_path = chunk_dir.join(chunk_file_name(hash));
+        let chunk_data = std::fs::read(&chunk_path).map_err(|e| {
+            Error::InvalidArchive(format!(
+                "missing chunk {} for '{}': {}",
+                chunk_file_name(hash),
+                entry.path.display(),
+                e
+            ))
+        })?;
+        data.extend_from_slice(&chunk_data);
+    }
+    if data.len() as u64 != entry.size {
+        return Err(Error::InvalidArchive(format!(
+            "'{}' reassembled to {} bytes but manifest declares {}",
+            entry.path.display(),
+            data.len(),
+            entry.size
+        )));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gear_table_is_nonzero_and_distinct() {
+        assert!(GEAR.iter().all(|&v| v != 0));
+        let mut sorted = GEAR.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), GEAR.len());
+    }
+
+    #[test]
+    fn test_cut_point_respects_min_and_max_size() {
+        let config = ChunkerConfig {
+            min_size: 16,
+            avg_size: 32,
+            max_size: 64,
+        };
+        let data = vec![0xABu8; 1000];
+        let mut offset = 0;
+        while offset < data.len() {
+            let len = cut_point(&data[offset..], &config);
+            let remaining = data.len() - offset;
+            if remaining > config.min_size {
+                assert!(len >= config.min_size);
+                assert!(len <= config.max_size);
+            }
+            assert!(len > 0);
+            offset += len;
+        }
+    }
+
+    #[test]
+    fn test_dedup_identical_files_share_all_chunks() {
+        let config = ChunkerConfig::default();
+        let content: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let files = vec![
+            (PathBuf::from("a.bin"), content.clone()),
+            (PathBuf::from("b.bin"), content.clone()),
+        ];
+        let result = dedup_files(&files, &config);
+
+        assert_eq!(result.files.len(), 2);
+        assert_eq!(result.files[0].chunk_indices, result.files[1].chunk_indices);
+        assert!(result.unique_chunks.len() < result.files[0].chunk_indices.len() * 2);
+    }
+
+    #[test]
+    fn test_dedup_distinguishes_different_content() {
+        let config = ChunkerConfig::default();
+        let files = vec![
+            (PathBuf::from("a.bin"), vec![1u8; 100_000]),
+            (PathBuf::from("b.bin"), vec![2u8; 100_000]),
+        ];
+        let result = dedup_files(&files, &config);
+
+        let a_hashes: Vec<_> = result.files[0]
+            .chunk_indices
+            .iter()
+            .map(|&i| result.unique_chunks[i].hash)
+            .collect();
+        let b_hashes: Vec<_> = result.files[1]
+            .chunk_indices
+            .iter()
+            .map(|&i| result.unique_chunks[i].hash)
+            .collect();
+        assert_ne!(a_hashes, b_hashes);
+    }
+
+    #[test]
+    fn test_empty_file_has_no_chunks() {
+        let config = ChunkerConfig::default();
+        let files = vec![(PathBuf::from("empty.bin"), Vec::new())];
+        let result = dedup_files(&files, &config);
+        assert!(result.files[0].chunk_indices.is_empty());
+        assert_eq!(result.files[0].size, 0);
+    }
+
+    #[test]
+    fn test_manifest_text_lists_every_file() {
+        let config = ChunkerConfig::default();
+        let files = vec![
+            (PathBuf::from("a.bin"), vec![1u8; 50_000]),
+            (PathBuf::from("b.bin"), vec![2u8; 50_000]),
+        ];
+        let result = dedup_files(&files, &config);
+        let text = result.manifest_text();
+        assert!(text.contains("a.bin"));
+        assert!(text.contains("b.bin"));
+        assert_eq!(text.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_chunk_hash_is_sha256_of_content() {
+        let hash = hash_chunk(b"hello world");
+        let expected: [u8; 32] = Sha256::digest(b"hello world").into();
+        assert_eq!(hash, expected);
+    }
+
+    #[test]
+    fn test_parse_manifest_round_trips_through_manifest_text() {
+        let config = ChunkerConfig::default();
+        let files = vec![
+            (PathBuf::from("a.bin"), vec![1u8; 50_000]),
+            (PathBuf::from("b.bin"), vec![2u8; 50_000]),
+        ];
+        let result = dedup_files(&files, &config);
+        let parsed = parse_manifest(&result.manifest_text()).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].path, PathBuf::from("a.bin"));
+        assert_eq!(parsed[0].size, 50_000);
+        let expected_hashes: Vec<[u8; 32]> = result.files[0]
+            .chunk_indices
+            .iter()
+            .map(|&i| result.unique_chunks[i].hash)
+            .collect();
+        assert_eq!(parsed[0].chunk_hashes, expected_hashes);
+    }
+
+    #[test]
+    fn test_parse_manifest_rejects_truncated_hash() {
+        let result = parse_manifest("a.bin\t5\tabcd");
+        assert!(matches!(result, Err(Error::InvalidArchive(_))));
+    }
+
+    #[test]
+    fn test_chunk_file_name_is_64_hex_chars_plus_extension() {
+        let name = chunk_file_name(&[0xABu8; 32]);
+        assert_eq!(name.len(), 64 + 4);
+        assert!(name.starts_with(&"ab".repeat(32)));
+        assert!(name.ends_with(".bin"));
+    }
+
+    #[test]
+    fn test_reassemble_file_round_trips_through_dedup_and_pool() {
+        let config = ChunkerConfig::default();
+        let content: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        let files = vec![(PathBuf::from("a.bin"), content.clone())];
+        let result = dedup_files(&files, &config);
+
+        let dir = std::env::temp_dir().join(format!(
+            "seven_zip_dedup_test_{}_{}",
+            std::process::id(),
+            content.len()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        for chunk in &result.unique_chunks {
+            std::fs::write(dir.join(chunk_file_name(&chunk.hash)), &chunk.data).unwrap();
+        }
+
+        let parsed = parse_manifest(&result.manifest_text()).unwrap();
+        let reassembled = reassemble_file(&parsed[0], &dir).unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(reassembled, content);
+    }
+
+    #[test]
+    fn test_reassemble_file_fails_on_missing_chunk() {
+        let entry = ManifestEntry {
+            path: PathBuf::from("a.bin"),
+            size: 10,
+            chunk_hashes: vec![[0u8; 32]],
+        };
+        let result = reassemble_file(&entry, &std::env::temp_dir().join("seven_zip_dedup_nonexistent"));
+        assert!(matches!(result, Err(Error::InvalidArchive(_))));
+    }
+}
@@ -10,6 +10,10 @@
 //! - Hardware-accelerated (AES-NI on supported CPUs)
 //! - Secure random IV and salt generation
 //! - PKCS#7 padding
+//! - Streaming encryption via [`EncryptionContext::encrypting_writer`] and
+//!   (whole-buffer-at-EOF) decryption via
+//!   [`DecryptionContext::decrypting_reader`], for payloads too large to
+//!   hold in memory as a single `Vec<u8>`
 //!
 //! # Example
 //!
@@ -32,6 +36,7 @@
 use crate::error::{Error, Result};
 use crate::ffi;
 use std::ffi::CString;
+use std::io::{Read, Write};
 
 /// AES-256 encryption context
 ///
@@ -241,6 +246,18 @@ impl EncryptionContext {
         plaintext.truncate(plaintext_len);
         Ok(plaintext)
     }
+
+    /// Wrap `writer` in a block-at-a-time [`EncryptingWriter`], so a large
+    /// payload can be encrypted without ever holding the whole thing (or its
+    /// ciphertext) in memory at once
+    ///
+    /// Clones this context's key schedule and current IV; the clone's IV
+    /// advances independently as blocks are written, so calling this more
+    /// than once on the same context produces independent streams that all
+    /// start from the IV [`Self::iv`] returned at construction time.
+    pub fn encrypting_writer<W: Write>(&self, writer: W) -> EncryptingWriter<W> {
+        EncryptingWriter::new(writer, self.aes_context.clone(), self.iv)
+    }
 }
 
 impl DecryptionContext {
@@ -342,6 +359,210 @@ impl DecryptionContext {
         plaintext.truncate(plaintext_len);
         Ok(plaintext)
     }
+
+    /// Wrap `reader` in a [`DecryptingReader`] that decrypts under this
+    /// context's key, seeded with `iv` (from the archive header, matching
+    /// the IV [`EncryptionContext::encrypt`] used)
+    pub fn decrypting_reader<R: Read>(
+        &self,
+        reader: R,
+        iv: &[u8; ffi::AES_BLOCK_SIZE],
+    ) -> DecryptingReader<R> {
+        DecryptingReader::new(reader, self.aes_context.clone(), *iv)
+    }
+}
+
+/// Block-at-a-time AES-256-CBC encryptor implementing [`Write`], backed by
+/// the C AES implementation via [`crate::ffi`]
+///
+/// [`EncryptionContext::encrypt`] takes a whole `&[u8]` and allocates a full
+/// copy, which is wasteful for multi-gigabyte payloads. This instead keeps
+/// at most one not-yet-encrypted block buffered: each [`Write::write`] call
+/// encrypts and emits every full block it can via `sevenzip_encrypt_data`,
+/// holding the last partial (or exactly-full) block back so [`Self::finish`]
+/// can apply PKCS#7 padding to it once the stream is known to be complete.
+///
+/// Every intermediate call still goes through `sevenzip_encrypt_data`, which
+/// always appends its own PKCS#7 padding block to whatever it's given —
+/// there's no lower-level "encrypt one block, no padding" entry point in the
+/// C API. This adapter works around that by feeding it only whole blocks,
+/// keeping the real (unpadded) ciphertext those blocks produce and
+/// discarding the synthetic trailing padding block each call adds, then
+/// chaining the next call's IV from the last real ciphertext block — the
+/// same result a true block-level CBC primitive would produce.
+pub struct EncryptingWriter<W: Write> {
+    writer: W,
+    aes_context: Box<[u32; ffi::AES_NUM_IVMRK_WORDS]>,
+    iv: [u8; ffi::AES_BLOCK_SIZE],
+    /// Plaintext buffered but not yet known to be the last block
+    pending: Vec<u8>,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    fn new(writer: W, aes_context: Box<[u32; ffi::AES_NUM_IVMRK_WORDS]>, iv: [u8; ffi::AES_BLOCK_SIZE]) -> Self {
+        Self {
+            writer,
+            aes_context,
+            iv,
+            pending: Vec::with_capacity(ffi::AES_BLOCK_SIZE),
+        }
+    }
+
+    /// Encrypt `plaintext` (a positive multiple of the block size) and
+    /// write only the real ciphertext blocks it produces, discarding the
+    /// extra padding block `sevenzip_encrypt_data` always appends
+    fn encrypt_full_blocks(&mut self, plaintext: &[u8]) -> Result<()> {
+        let padded_len = plaintext.len() + ffi::AES_BLOCK_SIZE;
+        let mut ciphertext = vec![0u8; padded_len];
+        let mut ciphertext_len = padded_len;
+
+        unsafe {
+            let result = ffi::sevenzip_encrypt_data(
+                self.aes_context.as_mut_ptr(),
+                self.iv.as_ptr(),
+                plaintext.as_ptr(),
+                plaintext.len(),
+                ciphertext.as_mut_ptr(),
+                &mut ciphertext_len as *mut usize,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(Error::from_code(result));
+            }
+        }
+
+        let real = &ciphertext[..plaintext.len()];
+        self.iv.copy_from_slice(&real[real.len() - ffi::AES_BLOCK_SIZE..]);
+        self.writer
+            .write_all(real)
+            .map_err(|e| Error::EncryptionError(e.to_string()))
+    }
+
+    /// Apply PKCS#7 padding to the held-back final block and flush it,
+    /// returning the wrapped writer
+    pub fn finish(mut self) -> Result<W> {
+        let padded_len = ((self.pending.len() / ffi::AES_BLOCK_SIZE) + 1) * ffi::AES_BLOCK_SIZE;
+        let mut ciphertext = vec![0u8; padded_len];
+        let mut ciphertext_len = padded_len;
+
+        unsafe {
+            let result = ffi::sevenzip_encrypt_data(
+                self.aes_context.as_mut_ptr(),
+                self.iv.as_ptr(),
+                self.pending.as_ptr(),
+                self.pending.len(),
+                ciphertext.as_mut_ptr(),
+                &mut ciphertext_len as *mut usize,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(Error::from_code(result));
+            }
+        }
+
+        ciphertext.truncate(ciphertext_len);
+        self.writer
+            .write_all(&ciphertext)
+            .map_err(|e| Error::EncryptionError(e.to_string()))?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+
+        // Always hold back at least one full block: it might be the last
+        // one, which `finish` needs intact to apply padding correctly.
+        if self.pending.len() > ffi::AES_BLOCK_SIZE {
+            let flushable = (self.pending.len() - 1) / ffi::AES_BLOCK_SIZE * ffi::AES_BLOCK_SIZE;
+            let chunk: Vec<u8> = self.pending.drain(..flushable).collect();
+            self.encrypt_full_blocks(&chunk)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Counterpart to [`EncryptingWriter`], implementing [`Read`] over an AES-256-CBC
+/// ciphertext stream decrypted via [`crate::ffi::sevenzip_decrypt_data`]
+///
+/// Unlike [`EncryptingWriter`], this can't decrypt incrementally: the C
+/// `sevenzip_decrypt_data` call always strips PKCS#7 padding from the final
+/// block of whatever buffer it's given, so calling it once per chunk would
+/// incorrectly strip real plaintext bytes from every chunk's end, not just
+/// the stream's true last block, and there's no lower-level primitive in
+/// the C API to disable that. This reader therefore reads the inner reader
+/// to completion and performs a single whole-buffer decrypt before serving
+/// any bytes — still memory-bounded on the *write* side via
+/// [`EncryptionContext::encrypting_writer`], but not on this read side. For
+/// genuinely incremental decryption, use
+/// [`crate::encryption_native::DecryptingReader`] instead, which decrypts
+/// with its own block cipher and doesn't have this limitation.
+pub struct DecryptingReader<R: Read> {
+    reader: R,
+    aes_context: Box<[u32; ffi::AES_NUM_IVMRK_WORDS]>,
+    iv: [u8; ffi::AES_BLOCK_SIZE],
+    ciphertext: Vec<u8>,
+    plaintext: Option<std::io::Cursor<Vec<u8>>>,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    fn new(reader: R, aes_context: Box<[u32; ffi::AES_NUM_IVMRK_WORDS]>, iv: [u8; ffi::AES_BLOCK_SIZE]) -> Self {
+        Self {
+            reader,
+            aes_context,
+            iv,
+            ciphertext: Vec::new(),
+            plaintext: None,
+        }
+    }
+
+    fn decrypt_all(&mut self) -> std::io::Result<()> {
+        self.reader.read_to_end(&mut self.ciphertext)?;
+        if self.ciphertext.len() % ffi::AES_BLOCK_SIZE != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "ciphertext length is not a multiple of 16 bytes",
+            ));
+        }
+
+        let mut plaintext = vec![0u8; self.ciphertext.len()];
+        let mut plaintext_len = self.ciphertext.len();
+
+        unsafe {
+            let result = ffi::sevenzip_decrypt_data(
+                self.aes_context.as_mut_ptr(),
+                self.iv.as_ptr(),
+                self.ciphertext.as_ptr(),
+                self.ciphertext.len(),
+                plaintext.as_mut_ptr(),
+                &mut plaintext_len as *mut usize,
+            );
+            if result != ffi::SevenZipErrorCode::SEVENZIP_OK {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    Error::from_code(result).to_string(),
+                ));
+            }
+        }
+
+        plaintext.truncate(plaintext_len);
+        self.plaintext = Some(std::io::Cursor::new(plaintext));
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.plaintext.is_none() {
+            self.decrypt_all()?;
+        }
+        self.plaintext.as_mut().expect("decrypt_all populates plaintext or returns early").read(buf)
+    }
 }
 
 /// Verify if a password is correct for an encrypted archive
@@ -450,4 +671,32 @@ mod tests {
         let result = DecryptionContext::new("password", &[]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_encrypting_writer_is_callable() {
+        // Only exercises real encryption if the C library is actually
+        // linked; otherwise `EncryptionContext::new` errors and the test is
+        // a no-op, same as `test_encryption_context_creation` above.
+        let Ok(enc_ctx) = EncryptionContext::new("stream_password") else {
+            return;
+        };
+        let mut ciphertext = Vec::new();
+        let mut writer = enc_ctx.encrypting_writer(&mut ciphertext);
+        writer.write_all(b"streamed plaintext").unwrap();
+        let result = writer.finish();
+        assert!(result.is_ok() || result.is_err());
+    }
+
+    #[test]
+    fn test_decrypting_reader_is_callable() {
+        let Ok(dec_ctx) = DecryptionContext::new("stream_password", &[1, 2, 3, 4, 5, 6, 7, 8])
+        else {
+            return;
+        };
+        let iv = [0u8; ffi::AES_BLOCK_SIZE];
+        let mut reader = dec_ctx.decrypting_reader(std::io::empty(), &iv);
+        let mut decrypted = Vec::new();
+        let result = reader.read_to_end(&mut decrypted);
+        assert!(result.is_ok() || result.is_err());
+    }
 }
@@ -5,16 +5,44 @@
 //!
 //! # Security
 //!
-//! - AES-256-CBC encryption
-//! - PBKDF2-SHA256 key derivation with 262,144 iterations (7-Zip compatible)
+//! - AES-256-CBC encryption (whole-buffer) and AES-256-CTR encryption
+//!   (streaming, for payloads too large to hold in memory)
+//! - AES-256-GCM authenticated encryption (one-pass confidentiality +
+//!   integrity, with optional associated data) via
+//!   [`EncryptionContext::encrypt_gcm`]/[`DecryptionContext::decrypt_gcm`]
+//! - PBKDF2-SHA256 key derivation with 262,144 iterations by default, or
+//!   the real 7-Zip rolling-SHA256 schedule via
+//!   [`EncryptionContext::new_7z_compatible`]/[`derive_key_7z`] for actual
+//!   interop with p7zip/7-Zip-produced archives
 //! - Secure random IV and salt generation
-//! - PKCS#7 padding
+//! - PKCS#7 padding (zero padding in 7-Zip-compatible mode, see
+//!   [`encrypt_7z`]/[`decrypt_7z`])
+//! - Encrypt-then-MAC via [`EncryptionContext::new_authenticated`], and
+//!   constant-time (no padding-oracle) password checking via
+//!   [`verify_password`]
+//! - WinZip AE-2 style authenticated AES-256-CTR via [`WinZipAesContext`]/
+//!   [`WinZipAesDecryptionContext`] — a 2-byte password-verification value
+//!   (mismatch returns [`Error::WrongPassword`]) checked before a truncated
+//!   HMAC-SHA1 over the ciphertext (mismatch returns
+//!   [`Error::AuthenticationFailed`]), so a wrong password is distinguished
+//!   from tampered data and neither returns any plaintext
 //! - Automatic key zeroization on drop
 
-use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use aes::cipher::{
+    block_padding::{NoPadding, Pkcs7}, generic_array::GenericArray, BlockDecrypt, BlockDecryptMut,
+    BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit,
+};
+use aes_gcm::{
+    aead::{Aead, KeyInit as GcmKeyInit, Payload},
+    Aes256Gcm, Key as GcmKey, Nonce as GcmNonce,
+};
+use argon2::{Algorithm, Argon2, Params, Version};
+use hmac::{Hmac, Mac};
 use pbkdf2::pbkdf2_hmac;
-use rand::RngCore;
-use sha2::Sha256;
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+use subtle::ConstantTimeEq;
 use zeroize::ZeroizeOnDrop;
 
 use crate::error::{Error, Result};
@@ -27,9 +55,117 @@ pub const AES_KEY_SIZE: usize = 32;
 pub const SALT_SIZE: usize = 16;
 /// PBKDF2 iterations (7-Zip default)
 pub const PBKDF2_ITERATIONS: u32 = 262_144;
+/// AES-256-GCM nonce size in bytes (96 bits, the size GCM is defined and
+/// optimized for)
+pub const GCM_NONCE_SIZE: usize = 12;
+/// AES-256-GCM authentication tag size in bytes
+pub const GCM_TAG_SIZE: usize = 16;
+/// HMAC-SHA256 tag size in bytes, appended by [`EncryptionContext::new_authenticated`]
+pub const MAC_TAG_SIZE: usize = 32;
+/// Default `num_cycles_power` for the real 7-Zip AES-256 KDF (2^19 ≈ 524,288 rounds)
+pub const SEVENZIP_DEFAULT_CYCLES_POWER: u8 = 19;
 
 type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
 type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+type HmacSha256 = Hmac<Sha256>;
+
+/// Key-derivation function and cost parameters, stored alongside a
+/// context's salt/IV so [`DecryptionContext::new_with_kdf`] can reconstruct
+/// the exact derivation used to encrypt
+///
+/// [`Self::Pbkdf2Sha256`] is this module's original default (262,144
+/// rounds), [`Self::SevenZipSha256`] is the real 7-Zip `07F10110` coder
+/// schedule (see [`derive_key_7z`]), and [`Self::Argon2id`] is a modern
+/// memory-hard alternative for new archives that don't need interop with
+/// either of those — legacy archives keep reading correctly because the
+/// KDF that produced them travels with the ciphertext rather than being
+/// assumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KdfParams {
+    /// PBKDF2-HMAC-SHA256 with the given round count
+    Pbkdf2Sha256 {
+        /// Number of PBKDF2 rounds
+        iterations: u32,
+    },
+    /// Argon2id, the memory-hard winner of the Password Hashing Competition
+    Argon2id {
+        /// Memory cost in KiB
+        mem_kib: u32,
+        /// Number of passes over memory
+        iterations: u32,
+        /// Degree of parallelism (lanes)
+        parallelism: u32,
+    },
+    /// The real 7-Zip AES-256 (`07F10110` coder) rolling-SHA256 schedule;
+    /// see [`derive_key_7z`]
+    SevenZipSha256 {
+        /// `log2` of the number of KDF rounds (7-Zip's own unit)
+        num_cycles_power: u8,
+    },
+}
+
+impl KdfParams {
+    /// A conservative Argon2id default: 64 MiB of memory, 3 passes, single
+    /// lane. Reasonable for interactive use on a desktop or server; tune
+    /// `mem_kib` down for constrained environments.
+    pub const fn argon2id_default() -> Self {
+        KdfParams::Argon2id {
+            mem_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+        }
+    }
+}
+
+impl Default for KdfParams {
+    /// This module's long-standing default: PBKDF2-SHA256, 262,144 rounds
+    fn default() -> Self {
+        KdfParams::Pbkdf2Sha256 {
+            iterations: PBKDF2_ITERATIONS,
+        }
+    }
+}
+
+/// Derive a 32-byte AES key from `password` and `salt` using whichever KDF
+/// `kdf` selects
+fn derive_key_with_kdf(password: &str, salt: &[u8], kdf: KdfParams) -> Result<[u8; AES_KEY_SIZE]> {
+    match kdf {
+        KdfParams::Pbkdf2Sha256 { iterations } => {
+            let mut key = [0u8; AES_KEY_SIZE];
+            pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key);
+            Ok(key)
+        }
+        KdfParams::SevenZipSha256 { num_cycles_power } => {
+            Ok(derive_key_7z(password, salt, num_cycles_power))
+        }
+        KdfParams::Argon2id { mem_kib, iterations, parallelism } => {
+            let params = Params::new(mem_kib, iterations, parallelism, Some(AES_KEY_SIZE))
+                .map_err(|e| {
+                    Error::InvalidParameter(format!("Invalid Argon2id parameters: {e}"))
+                })?;
+            let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+            let mut key = [0u8; AES_KEY_SIZE];
+            argon2
+                .hash_password_into(password.as_bytes(), salt, &mut key)
+                .map_err(|e| {
+                    Error::EncryptionError(format!("Argon2id key derivation failed: {e}"))
+                })?;
+            Ok(key)
+        }
+    }
+}
+
+/// Which padding/key-schedule a context uses, chosen by which constructor
+/// created it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PaddingMode {
+    /// This module's original scheme: PBKDF2-SHA256 key, PKCS#7 padding
+    Pkcs7,
+    /// The real 7-Zip (`07F10110` coder) scheme: rolling-SHA256 key (see
+    /// [`derive_key_7z`]), zero-padded plaintext with the true length
+    /// carried externally rather than recovered from the padding
+    ZeroPad7z,
+}
 
 /// AES-256 encryption context (pure Rust implementation)
 ///
@@ -42,6 +178,17 @@ pub struct EncryptionContext {
     key: [u8; AES_KEY_SIZE],
     iv: [u8; AES_BLOCK_SIZE],
     salt: [u8; SALT_SIZE],
+    /// Independent HMAC-SHA256 key, present only for contexts created with
+    /// [`EncryptionContext::new_authenticated`]. Its presence is what gates
+    /// encrypt-then-MAC behavior in [`Self::encrypt`]/[`Self::decrypt`].
+    #[zeroize(skip)]
+    mac_key: Option<[u8; AES_KEY_SIZE]>,
+    #[zeroize(skip)]
+    padding: PaddingMode,
+    /// The KDF and cost parameters that produced `key`, so a caller can
+    /// introspect and persist them alongside the salt/IV for later decryption
+    #[zeroize(skip)]
+    kdf: KdfParams,
 }
 
 impl EncryptionContext {
@@ -66,6 +213,16 @@ impl EncryptionContext {
     /// let ctx = EncryptionContext::new("StrongPassword123!").unwrap();
     /// ```
     pub fn new(password: &str) -> Result<Self> {
+        Self::new_with_rng(password, &mut OsRng)
+    }
+
+    /// Like [`Self::new`], but draws the random salt and IV from `rng`
+    /// instead of the OS CSPRNG
+    ///
+    /// Production code should always use [`Self::new`]; this exists so
+    /// tests can inject a deterministic RNG and get reproducible
+    /// salt/IV values instead of a fresh [`OsRng`] draw every run.
+    pub fn new_with_rng(password: &str, rng: &mut impl RngCore) -> Result<Self> {
         if password.is_empty() {
             return Err(Error::InvalidParameter(
                 "Password cannot be empty".to_string(),
@@ -77,14 +234,96 @@ impl EncryptionContext {
         let mut key = [0u8; AES_KEY_SIZE];
 
         // Generate random salt and IV
-        let mut rng = rand::thread_rng();
         rng.fill_bytes(&mut salt);
         rng.fill_bytes(&mut iv);
 
         // Derive key using PBKDF2-SHA256
         pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, PBKDF2_ITERATIONS, &mut key);
 
-        Ok(Self { key, iv, salt })
+        Ok(Self {
+            key,
+            iv,
+            salt,
+            mac_key: None,
+            padding: PaddingMode::Pkcs7,
+            kdf: KdfParams::default(),
+        })
+    }
+
+    /// Create a context using the real 7-Zip AES-256 (`07F10110` coder) key
+    /// schedule instead of this module's PBKDF2-SHA256 default
+    ///
+    /// Key derivation is [`derive_key_7z`], not PBKDF2: archives produced
+    /// with [`Self::new`] cannot be opened by p7zip/7-Zip, and vice versa.
+    /// 7-Zip CBC also doesn't use PKCS#7 — [`Self::encrypt`] zero-pads up to
+    /// a 16-byte multiple instead, and the true plaintext length must be
+    /// recovered by the caller from archive metadata (7-Zip stores it
+    /// alongside the coder properties), since [`Self::decrypt`] has no way
+    /// to tell trailing zero padding from real trailing zero bytes.
+    pub fn new_7z_compatible(password: &str, num_cycles_power: u8) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter(
+                "Password cannot be empty".to_string(),
+            ));
+        }
+
+        let mut salt = [0u8; SALT_SIZE];
+        let mut iv = [0u8; AES_BLOCK_SIZE];
+        let mut rng = OsRng;
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+
+        let key = derive_key_7z(password, &salt, num_cycles_power);
+
+        Ok(Self {
+            key,
+            iv,
+            salt,
+            mac_key: None,
+            padding: PaddingMode::ZeroPad7z,
+            kdf: KdfParams::SevenZipSha256 { num_cycles_power },
+        })
+    }
+
+    /// Create a new encryption context whose `encrypt`/`decrypt` calls are
+    /// authenticated (encrypt-then-MAC) rather than plain CBC
+    ///
+    /// Derives twice the PBKDF2-SHA256 output of [`Self::new`] (still
+    /// 262,144 iterations) and splits it into the AES key and an
+    /// independent HMAC-SHA256 key. [`Self::encrypt`] then appends a
+    /// 32-byte tag over `salt || iv || ciphertext`, and [`Self::decrypt`]
+    /// recomputes and checks that tag — returning
+    /// [`Error::AuthenticationFailed`] on a mismatch — before attempting to
+    /// strip padding, so a flipped ciphertext byte is detected instead of
+    /// silently producing garbage plaintext.
+    pub fn new_authenticated(password: &str) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter(
+                "Password cannot be empty".to_string(),
+            ));
+        }
+
+        let mut salt = [0u8; SALT_SIZE];
+        let mut iv = [0u8; AES_BLOCK_SIZE];
+        let mut rng = OsRng;
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+
+        let mut derived = [0u8; AES_KEY_SIZE * 2];
+        pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, PBKDF2_ITERATIONS, &mut derived);
+        let mut key = [0u8; AES_KEY_SIZE];
+        let mut mac_key = [0u8; AES_KEY_SIZE];
+        key.copy_from_slice(&derived[..AES_KEY_SIZE]);
+        mac_key.copy_from_slice(&derived[AES_KEY_SIZE..]);
+
+        Ok(Self {
+            key,
+            iv,
+            salt,
+            mac_key: Some(mac_key),
+            padding: PaddingMode::Pkcs7,
+            kdf: KdfParams::default(),
+        })
     }
 
     /// Create encryption context with specific salt and IV (for testing/compatibility)
@@ -109,6 +348,42 @@ impl EncryptionContext {
             key,
             iv: *iv,
             salt: salt_arr,
+            mac_key: None,
+            padding: PaddingMode::Pkcs7,
+            kdf: KdfParams::default(),
+        })
+    }
+
+    /// Create an encryption context using a specific [`KdfParams`] instead
+    /// of this module's fixed PBKDF2-SHA256 default
+    ///
+    /// Lets a caller opt into a memory-hard KDF (e.g.
+    /// [`KdfParams::argon2id_default`]) for new archives while old ones
+    /// created with [`Self::new`] stay readable via
+    /// [`DecryptionContext::new_with_kdf`], since the KDF choice travels
+    /// with the salt/IV rather than being assumed at decrypt time.
+    pub fn with_kdf(password: &str, kdf: KdfParams) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter(
+                "Password cannot be empty".to_string(),
+            ));
+        }
+
+        let mut salt = [0u8; SALT_SIZE];
+        let mut iv = [0u8; AES_BLOCK_SIZE];
+        let mut rng = OsRng;
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+
+        let key = derive_key_with_kdf(password, &salt, kdf)?;
+
+        Ok(Self {
+            key,
+            iv,
+            salt,
+            mac_key: None,
+            padding: PaddingMode::Pkcs7,
+            kdf,
         })
     }
 
@@ -126,6 +401,11 @@ impl EncryptionContext {
         &self.salt
     }
 
+    /// Get the KDF and cost parameters used to derive this context's key
+    pub fn kdf(&self) -> KdfParams {
+        self.kdf
+    }
+
     /// Get the derived encryption key
     ///
     /// **WARNING**: Exposing the key can compromise security. Only use for
@@ -155,17 +435,28 @@ impl EncryptionContext {
     /// assert_eq!(ciphertext.len() % 16, 0);
     /// ```
     pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
-        // Calculate buffer size (PKCS#7 adds 1-16 bytes of padding)
-        let padded_len = ((plaintext.len() / AES_BLOCK_SIZE) + 1) * AES_BLOCK_SIZE;
-        let mut buffer = vec![0u8; padded_len];
-        buffer[..plaintext.len()].copy_from_slice(plaintext);
+        let mut ciphertext = match self.padding {
+            PaddingMode::Pkcs7 => {
+                // Calculate buffer size (PKCS#7 adds 1-16 bytes of padding)
+                let padded_len = ((plaintext.len() / AES_BLOCK_SIZE) + 1) * AES_BLOCK_SIZE;
+                let mut buffer = vec![0u8; padded_len];
+                buffer[..plaintext.len()].copy_from_slice(plaintext);
 
-        let cipher = Aes256CbcEnc::new(&self.key.into(), &self.iv.into());
-        let ciphertext = cipher
-            .encrypt_padded_mut::<Pkcs7>(&mut buffer, plaintext.len())
-            .map_err(|_| Error::EncryptionError("Encryption failed".to_string()))?;
+                let cipher = Aes256CbcEnc::new(&self.key.into(), &self.iv.into());
+                cipher
+                    .encrypt_padded_mut::<Pkcs7>(&mut buffer, plaintext.len())
+                    .map_err(|_| Error::EncryptionError("Encryption failed".to_string()))?
+                    .to_vec()
+            }
+            PaddingMode::ZeroPad7z => encrypt_7z(&self.key, &self.iv, plaintext),
+        };
+
+        if let Some(mac_key) = &self.mac_key {
+            let tag = compute_mac(mac_key, &self.salt, &self.iv, &ciphertext);
+            ciphertext.extend_from_slice(&tag);
+        }
 
-        Ok(ciphertext.to_vec())
+        Ok(ciphertext)
     }
 
     /// Decrypt data using AES-256-CBC and verify PKCS#7 padding
@@ -189,22 +480,496 @@ impl EncryptionContext {
     /// let decrypted = ctx.decrypt(&ciphertext).unwrap();
     /// assert_eq!(plaintext.as_slice(), decrypted.as_slice());
     /// ```
+    ///
+    /// For a context created with [`Self::new_authenticated`], `ciphertext`
+    /// must have the 32-byte HMAC-SHA256 tag appended by [`Self::encrypt`];
+    /// the tag is checked (in constant time) before padding is touched, and
+    /// a mismatch returns [`Error::AuthenticationFailed`] rather than the
+    /// padding-failure [`Error::WrongPassword`] a corrupted ciphertext
+    /// would otherwise produce.
     pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
-        if ciphertext.len() % AES_BLOCK_SIZE != 0 {
+        let body = match &self.mac_key {
+            Some(mac_key) => {
+                if ciphertext.len() < MAC_TAG_SIZE {
+                    return Err(Error::AuthenticationFailed(
+                        "Ciphertext too short to contain a MAC tag".to_string(),
+                    ));
+                }
+                let (body, tag) = ciphertext.split_at(ciphertext.len() - MAC_TAG_SIZE);
+                let expected = compute_mac(mac_key, &self.salt, &self.iv, body);
+                if !constant_time_eq(&expected, tag) {
+                    return Err(Error::AuthenticationFailed(
+                        "HMAC tag verification failed (wrong password or tampered data)"
+                            .to_string(),
+                    ));
+                }
+                body
+            }
+            None => ciphertext,
+        };
+
+        if body.len() % AES_BLOCK_SIZE != 0 {
             return Err(Error::InvalidParameter(
                 "Ciphertext length must be multiple of 16 bytes".to_string(),
             ));
         }
 
-        let mut buffer = ciphertext.to_vec();
-        let cipher = Aes256CbcDec::new(&self.key.into(), &self.iv.into());
-        
-        let plaintext = cipher
-            .decrypt_padded_mut::<Pkcs7>(&mut buffer)
-            .map_err(|_| Error::DecryptionError("Decryption failed (wrong password?)".to_string()))?;
+        match self.padding {
+            PaddingMode::Pkcs7 => {
+                let mut buffer = body.to_vec();
+                let cipher = Aes256CbcDec::new(&self.key.into(), &self.iv.into());
 
-        Ok(plaintext.to_vec())
+                let plaintext = cipher
+                    .decrypt_padded_mut::<Pkcs7>(&mut buffer)
+                    .map_err(|_| {
+                        Error::WrongPassword(
+                            "PKCS#7 padding was invalid after decryption".to_string(),
+                        )
+                    })?;
+
+                Ok(plaintext.to_vec())
+            }
+            // No PKCS#7 to strip: the true length lives in archive metadata
+            // the caller must truncate to, same as real 7-Zip.
+            PaddingMode::ZeroPad7z => decrypt_7z_raw(&self.key, &self.iv, body),
+        }
+    }
+
+    /// Wrap `writer` in an [`EncryptingWriter`] seeded from this context's
+    /// key and IV
+    ///
+    /// Unlike [`Self::encrypt_stream`] (AES-256-CTR with a fresh nonce per
+    /// call), this drives the same AES-256-CBC feedback chain [`Self::encrypt`]
+    /// uses, one block at a time, so a caller can pipe e.g. `create_archive`'s
+    /// output straight through encryption without buffering the whole payload
+    /// in memory first.
+    ///
+    /// Returns [`Error::InvalidParameter`] for a context built with
+    /// [`Self::new_authenticated`] or [`Self::new_7z_compatible`]: the MAC tag
+    /// and zero-padding schemes those contexts use are computed over (or rely
+    /// on knowing) the whole ciphertext up front, which an incremental writer
+    /// can't provide until the stream is known to be complete.
+    pub fn encrypting_writer<W: Write>(&self, writer: W) -> Result<EncryptingWriter<W>> {
+        if self.mac_key.is_some() || self.padding != PaddingMode::Pkcs7 {
+            return Err(Error::InvalidParameter(
+                "encrypting_writer only supports a plain PKCS#7 context (not new_authenticated or new_7z_compatible)".to_string(),
+            ));
+        }
+        Ok(EncryptingWriter::new(writer, self.key, self.iv))
+    }
+
+    /// Encrypt a `Read` stream to a `Write` sink using AES-256-CTR
+    ///
+    /// Unlike [`EncryptionContext::encrypt`], this never materializes the
+    /// whole payload in memory, so it scales to multi-gigabyte inputs. A
+    /// fresh random 16-byte nonce is generated per call and written as a
+    /// prefix to `writer`; each 16-byte block of keystream is produced by
+    /// encrypting the big-endian nonce treated as a 128-bit counter,
+    /// incremented once per block, and XORed with the plaintext, so no
+    /// padding is needed and the final partial block is simply truncated.
+    ///
+    /// # Security
+    ///
+    /// Reusing a (key, nonce) pair is catastrophic for CTR mode — it
+    /// reveals the XOR of the two plaintexts. Always let this method draw
+    /// its own fresh nonce; never persist and reuse one across calls.
+    pub fn encrypt_stream<R: Read, W: Write>(&self, mut reader: R, mut writer: W) -> Result<()> {
+        let mut nonce = [0u8; AES_BLOCK_SIZE];
+        OsRng.fill_bytes(&mut nonce);
+        writer.write_all(&nonce).map_err(|e| Error::EncryptionError(e.to_string()))?;
+
+        let counter = u128::from_be_bytes(nonce);
+        ctr_transform(&self.key, counter, &mut reader, &mut writer)
+            .map_err(|e| Error::EncryptionError(e.to_string()))
+    }
+
+    /// Decrypt a stream produced by [`EncryptionContext::encrypt_stream`]
+    ///
+    /// Reads the 16-byte nonce prefix from `reader`, then applies the same
+    /// AES-256-CTR keystream to recover the plaintext.
+    pub fn decrypt_stream<R: Read, W: Write>(&self, mut reader: R, mut writer: W) -> Result<()> {
+        let mut nonce = [0u8; AES_BLOCK_SIZE];
+        reader
+            .read_exact(&mut nonce)
+            .map_err(|e| Error::DecryptionError(e.to_string()))?;
+
+        let counter = u128::from_be_bytes(nonce);
+        ctr_transform(&self.key, counter, &mut reader, &mut writer)
+            .map_err(|e| Error::DecryptionError(e.to_string()))
+    }
+
+    /// Encrypt `plaintext` with AES-256-GCM, authenticating `aad`
+    /// (associated data — e.g. an archive header or volume index) without
+    /// encrypting it
+    ///
+    /// Unlike [`Self::encrypt`]'s CBC path, GCM needs no padding (it's a
+    /// CTR-mode stream cipher under the hood) and folds integrity into the
+    /// same pass via a 128-bit tag, so there's no separate encrypt-then-MAC
+    /// step like [`Self::new_authenticated`] needs. A fresh random
+    /// [`GCM_NONCE_SIZE`]-byte nonce is generated per call and prefixed to
+    /// the output: `nonce ‖ ciphertext ‖ tag`.
+    ///
+    /// # Security
+    ///
+    /// Reusing a (key, nonce) pair is catastrophic for GCM, exactly as for
+    /// the CTR mode [`Self::encrypt_stream`] uses — never persist and reuse
+    /// one; always let this method draw its own fresh nonce.
+    pub fn encrypt_gcm(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let cipher = Aes256Gcm::new(GcmKey::<Aes256Gcm>::from_slice(&self.key));
+        let mut nonce_bytes = [0u8; GCM_NONCE_SIZE];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = GcmNonce::from_slice(&nonce_bytes);
+
+        let ciphertext = cipher
+            .encrypt(nonce, Payload { msg: plaintext, aad })
+            .map_err(|_| Error::EncryptionError("AES-256-GCM encryption failed".to_string()))?;
+
+        let mut out = Vec::with_capacity(GCM_NONCE_SIZE + ciphertext.len());
+        out.extend_from_slice(&nonce_bytes);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+}
+
+/// Compute the HMAC-SHA256 tag over `salt || iv || ciphertext` used by
+/// [`EncryptionContext::new_authenticated`]'s encrypt-then-MAC scheme
+fn compute_mac(mac_key: &[u8; AES_KEY_SIZE], salt: &[u8], iv: &[u8], ciphertext: &[u8]) -> [u8; MAC_TAG_SIZE] {
+    let mut mac = HmacSha256::new_from_slice(mac_key).expect("HMAC accepts any key length");
+    mac.update(salt);
+    mac.update(iv);
+    mac.update(ciphertext);
+    mac.finalize().into_bytes().into()
+}
+
+/// Compare two equal-length byte slices without branching on the first
+/// differing byte, to avoid leaking comparison timing for the MAC check
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
     }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Core AES-256-CTR transform shared by encryption and decryption: XORing
+/// with the same keystream is its own inverse, so `reader` -> `writer` is
+/// identical either direction once the starting counter is known.
+fn ctr_transform(
+    key: &[u8; AES_KEY_SIZE],
+    mut counter: u128,
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+) -> std::io::Result<()> {
+    let cipher = aes::Aes256::new(GenericArray::from_slice(key));
+    let mut buf = [0u8; 4096];
+
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+
+        let mut offset = 0;
+        while offset < n {
+            let block_len = (n - offset).min(AES_BLOCK_SIZE);
+
+            let mut keystream = GenericArray::clone_from_slice(&counter.to_be_bytes());
+            cipher.encrypt_block(&mut keystream);
+            counter = counter.wrapping_add(1);
+
+            for i in 0..block_len {
+                buf[offset + i] ^= keystream[i];
+            }
+            writer.write_all(&buf[offset..offset + block_len])?;
+            offset += block_len;
+        }
+    }
+
+    Ok(())
+}
+
+/// Block-at-a-time AES-256-CBC encryptor implementing [`Write`]
+///
+/// [`EncryptionContext::encrypt`] takes a whole `&[u8]` and allocates a full
+/// copy, which defeats streaming gigabyte-sized payloads through memory a
+/// block at a time. This instead keeps a 16-byte CBC feedback register and
+/// a buffer of at most one not-yet-encrypted block; each `write` call
+/// encrypts and emits every full block it can, holding the last partial (or
+/// exactly-full) block back so [`Self::finish`] can apply PKCS#7 padding to
+/// it once the stream is known to be complete.
+pub struct EncryptingWriter<W: Write> {
+    writer: W,
+    key: [u8; AES_KEY_SIZE],
+    feedback: [u8; AES_BLOCK_SIZE],
+    pending: Vec<u8>,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    /// Wrap `writer`, encrypting under `key` with CBC feedback seeded from `iv`
+    pub fn new(writer: W, key: [u8; AES_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) -> Self {
+        Self {
+            writer,
+            key,
+            feedback: iv,
+            pending: Vec::with_capacity(AES_BLOCK_SIZE),
+        }
+    }
+
+    fn encrypt_and_emit(&mut self, mut block: [u8; AES_BLOCK_SIZE]) -> std::io::Result<()> {
+        for (b, f) in block.iter_mut().zip(self.feedback.iter()) {
+            *b ^= f;
+        }
+        let cipher = aes::Aes256::new(GenericArray::from_slice(&self.key));
+        let mut ga = GenericArray::clone_from_slice(&block);
+        cipher.encrypt_block(&mut ga);
+        self.feedback.copy_from_slice(&ga);
+        self.writer.write_all(&ga)
+    }
+
+    /// Apply PKCS#7 padding to the held-back final block and flush it,
+    /// returning the wrapped writer
+    ///
+    /// If exactly one full block was pending, it's emitted as-is followed
+    /// by a whole extra padding block (the PKCS#7 rule for already-aligned
+    /// input), matching [`EncryptionContext::encrypt`]'s padding behavior.
+    pub fn finish(mut self) -> Result<W> {
+        if self.pending.len() == AES_BLOCK_SIZE {
+            let mut block = [0u8; AES_BLOCK_SIZE];
+            block.copy_from_slice(&self.pending);
+            self.encrypt_and_emit(block)
+                .map_err(|e| Error::EncryptionError(e.to_string()))?;
+            self.encrypt_and_emit([AES_BLOCK_SIZE as u8; AES_BLOCK_SIZE])
+                .map_err(|e| Error::EncryptionError(e.to_string()))?;
+        } else {
+            let pad_len = (AES_BLOCK_SIZE - self.pending.len()) as u8;
+            let mut block = [pad_len; AES_BLOCK_SIZE];
+            block[..self.pending.len()].copy_from_slice(&self.pending);
+            self.encrypt_and_emit(block)
+                .map_err(|e| Error::EncryptionError(e.to_string()))?;
+        }
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+
+        // Always hold back at least one full block: it might be the last
+        // one, which `finish` needs intact to apply padding correctly.
+        while self.pending.len() > AES_BLOCK_SIZE {
+            let mut block = [0u8; AES_BLOCK_SIZE];
+            block.copy_from_slice(&self.pending[..AES_BLOCK_SIZE]);
+            self.encrypt_and_emit(block)?;
+            self.pending.drain(..AES_BLOCK_SIZE);
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Block-at-a-time AES-256-CBC decryptor implementing [`Read`]
+///
+/// The counterpart to [`EncryptingWriter`]: decodes one 16-byte ciphertext
+/// block at a time as they're read, rather than requiring the whole
+/// ciphertext up front like [`DecryptionContext::decrypt`]. Because PKCS#7
+/// padding lives in the final block, the most recently decrypted block is
+/// held back until either another block arrives (proving it wasn't last) or
+/// the inner reader reaches EOF, at which point padding is stripped from it.
+pub struct DecryptingReader<R: Read> {
+    reader: R,
+    key: [u8; AES_KEY_SIZE],
+    feedback: [u8; AES_BLOCK_SIZE],
+    /// Raw ciphertext bytes read but not yet assembled into a full block
+    cipher_partial: Vec<u8>,
+    /// Decrypted block not yet known to be the last one
+    held_block: Option<[u8; AES_BLOCK_SIZE]>,
+    /// Plaintext bytes ready to be handed out by `read`
+    output: std::collections::VecDeque<u8>,
+    eof: bool,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    /// Wrap `reader`, decrypting under `key` with CBC feedback seeded from `iv`
+    pub fn new(reader: R, key: [u8; AES_KEY_SIZE], iv: [u8; AES_BLOCK_SIZE]) -> Self {
+        Self {
+            reader,
+            key,
+            feedback: iv,
+            cipher_partial: Vec::with_capacity(AES_BLOCK_SIZE),
+            held_block: None,
+            output: std::collections::VecDeque::new(),
+            eof: false,
+        }
+    }
+
+    fn decrypt_block(&mut self, block: [u8; AES_BLOCK_SIZE]) -> [u8; AES_BLOCK_SIZE] {
+        let cipher = aes::Aes256::new(GenericArray::from_slice(&self.key));
+        let mut ga = GenericArray::clone_from_slice(&block);
+        cipher.decrypt_block(&mut ga);
+        for (b, f) in ga.iter_mut().zip(self.feedback.iter()) {
+            *b ^= f;
+        }
+        self.feedback = block;
+        let mut out = [0u8; AES_BLOCK_SIZE];
+        out.copy_from_slice(&ga);
+        out
+    }
+
+    /// Pull and decrypt more ciphertext blocks until either the inner
+    /// reader is exhausted or at least one byte of plaintext is available
+    fn fill(&mut self) -> std::io::Result<()> {
+        while self.output.is_empty() && !self.eof {
+            let mut chunk = [0u8; 4096];
+            let n = self.reader.read(&mut chunk)?;
+            if n == 0 {
+                self.eof = true;
+                if !self.cipher_partial.is_empty() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "ciphertext length is not a multiple of 16 bytes",
+                    ));
+                }
+                if let Some(block) = self.held_block.take() {
+                    let pad_len = *block.last().unwrap() as usize;
+                    if pad_len == 0 || pad_len > AES_BLOCK_SIZE {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "invalid PKCS#7 padding",
+                        ));
+                    }
+                    self.output.extend(&block[..AES_BLOCK_SIZE - pad_len]);
+                }
+                break;
+            }
+
+            self.cipher_partial.extend_from_slice(&chunk[..n]);
+            while self.cipher_partial.len() >= AES_BLOCK_SIZE {
+                let mut block = [0u8; AES_BLOCK_SIZE];
+                block.copy_from_slice(&self.cipher_partial[..AES_BLOCK_SIZE]);
+                self.cipher_partial.drain(..AES_BLOCK_SIZE);
+
+                let plain = self.decrypt_block(block);
+                if let Some(previous) = self.held_block.replace(plain) {
+                    self.output.extend(&previous);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.output.is_empty() {
+            self.fill()?;
+        }
+        let n = self.output.len().min(buf.len());
+        for slot in buf.iter_mut().take(n) {
+            *slot = self.output.pop_front().unwrap();
+        }
+        Ok(n)
+    }
+}
+
+/// Seekable AES-256-CTR decryption context for random access into large
+/// encrypted payloads
+///
+/// [`DecryptionContext::decrypt`] only does whole-buffer AES-256-CBC, which
+/// forces decrypting everything before the part a caller actually wants —
+/// wasteful when reading one file out of a large encrypted archive. This
+/// context instead derives its key with the same PBKDF2-SHA256 (262,144
+/// iterations) schedule but operates in CTR mode, so [`Self::decrypt`] can
+/// be called on any byte range after a [`Self::seek`] without touching the
+/// bytes that precede it. The counter block for plaintext offset `o` is the
+/// nonce (the IV passed to [`Self::new`], matching
+/// [`EncryptionContext::encrypt_stream`]'s convention) plus `o / 16`.
+#[derive(ZeroizeOnDrop)]
+pub struct CtrDecryptionContext {
+    #[zeroize(skip)]
+    key: [u8; AES_KEY_SIZE],
+    #[zeroize(skip)]
+    base_counter: u128,
+    position: u64,
+}
+
+impl CtrDecryptionContext {
+    /// Create a context from a password, salt, and the nonce the payload
+    /// was encrypted under (e.g. the 16-byte prefix written by
+    /// [`EncryptionContext::encrypt_stream`])
+    pub fn new(password: &str, salt: &[u8], nonce: &[u8; AES_BLOCK_SIZE]) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter(
+                "Password cannot be empty".to_string(),
+            ));
+        }
+        if salt.is_empty() {
+            return Err(Error::InvalidParameter("Salt cannot be empty".to_string()));
+        }
+
+        let mut key = [0u8; AES_KEY_SIZE];
+        pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, PBKDF2_ITERATIONS, &mut key);
+
+        Ok(Self {
+            key,
+            base_counter: u128::from_be_bytes(*nonce),
+            position: 0,
+        })
+    }
+
+    /// Jump to `byte_offset` in the plaintext stream; the next
+    /// [`Self::decrypt`] call starts from there without processing any of
+    /// the bytes before it
+    pub fn seek(&mut self, byte_offset: u64) {
+        self.position = byte_offset;
+    }
+
+    /// Decrypt `ciphertext`, treating it as the bytes found at the
+    /// context's current position, and advance the position past it
+    ///
+    /// Only the requested range is processed: a non-block-aligned starting
+    /// offset decrypts just its containing 16-byte block and discards the
+    /// leading keystream bytes that precede it, rather than replaying the
+    /// keystream from the start of the stream.
+    pub fn decrypt(&mut self, ciphertext: &[u8]) -> Vec<u8> {
+        let out = ctr_xor_at(&self.key, self.base_counter, self.position, ciphertext);
+        self.position += ciphertext.len() as u64;
+        out
+    }
+}
+
+/// Apply the AES-256-CTR keystream for plaintext offset `offset` through
+/// `offset + data.len()` to `data`, starting from `base_counter` (the
+/// counter value for offset 0). Shared by [`CtrDecryptionContext::decrypt`]
+/// and [`DecryptionContext::decrypt_at`] so both seekable-decryption entry
+/// points derive each block's keystream identically.
+fn ctr_xor_at(key: &[u8; AES_KEY_SIZE], base_counter: u128, offset: u64, data: &[u8]) -> Vec<u8> {
+    let cipher = aes::Aes256::new(GenericArray::from_slice(key));
+    let mut out = Vec::with_capacity(data.len());
+
+    let mut offset = offset;
+    let mut remaining = data;
+    while !remaining.is_empty() {
+        let block_index = (offset / AES_BLOCK_SIZE as u64) as u128;
+        let intra_block_offset = (offset % AES_BLOCK_SIZE as u64) as usize;
+
+        let counter = base_counter.wrapping_add(block_index);
+        let mut keystream = GenericArray::clone_from_slice(&counter.to_be_bytes());
+        cipher.encrypt_block(&mut keystream);
+
+        let take = (AES_BLOCK_SIZE - intra_block_offset).min(remaining.len());
+        for (i, byte) in remaining[..take].iter().enumerate() {
+            out.push(byte ^ keystream[intra_block_offset + i]);
+        }
+
+        offset += take as u64;
+        remaining = &remaining[take..];
+    }
+
+    out
 }
 
 /// AES-256 decryption context (pure Rust implementation)
@@ -215,6 +980,11 @@ impl EncryptionContext {
 pub struct DecryptionContext {
     #[zeroize(skip)]
     key: [u8; AES_KEY_SIZE],
+    #[zeroize(skip)]
+    kdf: KdfParams,
+    /// Present only for a context created with [`Self::new_authenticated`];
+    /// gates [`Self::decrypt_authenticated`]
+    mac_key: Option<[u8; AES_KEY_SIZE]>,
 }
 
 impl DecryptionContext {
@@ -253,57 +1023,496 @@ impl DecryptionContext {
         let mut key = [0u8; AES_KEY_SIZE];
         pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, PBKDF2_ITERATIONS, &mut key);
 
-        Ok(Self { key })
+        Ok(Self { key, kdf: KdfParams::default(), mac_key: None })
     }
 
-    /// Get the derived decryption key
-    pub fn key(&self) -> &[u8; AES_KEY_SIZE] {
-        &self.key
+    /// Create a context using the real 7-Zip AES-256 key schedule (see
+    /// [`derive_key_7z`]) instead of PBKDF2, to decrypt archives produced by
+    /// [`EncryptionContext::new_7z_compatible`] or genuine 7-Zip
+    pub fn new_7z_compatible(password: &str, salt: &[u8], num_cycles_power: u8) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter(
+                "Password cannot be empty".to_string(),
+            ));
+        }
+        if salt.is_empty() {
+            return Err(Error::InvalidParameter("Salt cannot be empty".to_string()));
+        }
+
+        Ok(Self {
+            key: derive_key_7z(password, salt, num_cycles_power),
+            kdf: KdfParams::SevenZipSha256 { num_cycles_power },
+            mac_key: None,
+        })
     }
 
-    /// Decrypt data using AES-256-CBC
+    /// Create a context using an explicit [`KdfParams`], to decrypt archives
+    /// produced by [`EncryptionContext::with_kdf`]
     ///
-    /// # Arguments
-    ///
-    /// * `ciphertext` - Encrypted data
-    /// * `iv` - Initialization vector from archive header (16 bytes)
-    ///
-    /// # Returns
-    ///
-    /// Decrypted data with padding removed
-    pub fn decrypt(&self, ciphertext: &[u8], iv: &[u8; AES_BLOCK_SIZE]) -> Result<Vec<u8>> {
-        if ciphertext.len() % AES_BLOCK_SIZE != 0 {
+    /// `kdf` must match whatever the encrypting side recorded alongside its
+    /// salt/IV (e.g. [`EncryptionContext::kdf`]) — there's no way to recover
+    /// it from the ciphertext alone.
+    pub fn new_with_kdf(password: &str, salt: &[u8], kdf: KdfParams) -> Result<Self> {
+        if password.is_empty() {
             return Err(Error::InvalidParameter(
-                "Ciphertext length must be multiple of 16 bytes".to_string(),
+                "Password cannot be empty".to_string(),
+            ));
+        }
+        if salt.is_empty() {
+            return Err(Error::InvalidParameter("Salt cannot be empty".to_string()));
+        }
+
+        Ok(Self {
+            key: derive_key_with_kdf(password, salt, kdf)?,
+            kdf,
+            mac_key: None,
+        })
+    }
+
+    /// Create a context for archives encrypted with
+    /// [`EncryptionContext::new_authenticated`]
+    ///
+    /// Derives both the AES key and the independent HMAC-SHA256 key the same
+    /// way [`EncryptionContext::new_authenticated`] does, so
+    /// [`Self::decrypt_authenticated`] can verify the encrypt-then-MAC tag
+    /// before trusting any decrypted bytes. A context built with any other
+    /// constructor can't call [`Self::decrypt_authenticated`].
+    pub fn new_authenticated(password: &str, salt: &[u8]) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter(
+                "Password cannot be empty".to_string(),
+            ));
+        }
+        if salt.is_empty() {
+            return Err(Error::InvalidParameter("Salt cannot be empty".to_string()));
+        }
+
+        let mut derived = [0u8; AES_KEY_SIZE * 2];
+        pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, PBKDF2_ITERATIONS, &mut derived);
+        let mut key = [0u8; AES_KEY_SIZE];
+        key.copy_from_slice(&derived[..AES_KEY_SIZE]);
+        let mut mac_key = [0u8; AES_KEY_SIZE];
+        mac_key.copy_from_slice(&derived[AES_KEY_SIZE..]);
+
+        Ok(Self {
+            key,
+            kdf: KdfParams::default(),
+            mac_key: Some(mac_key),
+        })
+    }
+
+    /// Get the KDF and cost parameters this context's key was derived with
+    pub fn kdf(&self) -> KdfParams {
+        self.kdf
+    }
+
+    /// Get the derived decryption key
+    pub fn key(&self) -> &[u8; AES_KEY_SIZE] {
+        &self.key
+    }
+
+    /// Decrypt data using AES-256-CBC
+    ///
+    /// # Arguments
+    ///
+    /// * `ciphertext` - Encrypted data
+    /// * `iv` - Initialization vector from archive header (16 bytes)
+    ///
+    /// # Returns
+    ///
+    /// Decrypted data with padding removed
+    pub fn decrypt(&self, ciphertext: &[u8], iv: &[u8; AES_BLOCK_SIZE]) -> Result<Vec<u8>> {
+        if ciphertext.len() % AES_BLOCK_SIZE != 0 {
+            return Err(Error::InvalidParameter(
+                "Ciphertext length must be multiple of 16 bytes".to_string(),
             ));
         }
 
         let mut buffer = ciphertext.to_vec();
         let cipher = Aes256CbcDec::new(&self.key.into(), iv.into());
-        
+
         let plaintext = cipher
             .decrypt_padded_mut::<Pkcs7>(&mut buffer)
-            .map_err(|_| Error::DecryptionError("Decryption failed (wrong password?)".to_string()))?;
+            .map_err(|_| {
+                Error::WrongPassword("PKCS#7 padding was invalid after decryption".to_string())
+            })?;
 
         Ok(plaintext.to_vec())
     }
+
+    /// Verify and decrypt ciphertext produced by
+    /// [`EncryptionContext::new_authenticated`]'s `encrypt`
+    ///
+    /// Recomputes the HMAC-SHA256 tag over `ciphertext_and_tag`'s body and
+    /// compares it against the trailing [`MAC_TAG_SIZE`] bytes using
+    /// [`subtle::ConstantTimeEq`] *before* touching CBC/PKCS#7 at all,
+    /// returning [`Error::AuthenticationFailed`] on any mismatch — a wrong
+    /// password or a tampered ciphertext — rather than the
+    /// padding-failure [`Error::WrongPassword`] an unauthenticated
+    /// mismatch would eventually produce.
+    ///
+    /// `salt` and `iv` must be the same archive-header values
+    /// [`Self::new_authenticated`] and the encryption side's IV were built
+    /// from. Requires a context created with [`Self::new_authenticated`];
+    /// any other constructor returns [`Error::InvalidParameter`].
+    pub fn decrypt_authenticated(
+        &self,
+        ciphertext_and_tag: &[u8],
+        salt: &[u8],
+        iv: &[u8; AES_BLOCK_SIZE],
+    ) -> Result<Vec<u8>> {
+        let mac_key = self.mac_key.as_ref().ok_or_else(|| {
+            Error::InvalidParameter(
+                "decrypt_authenticated requires a context created with new_authenticated"
+                    .to_string(),
+            )
+        })?;
+        if ciphertext_and_tag.len() < MAC_TAG_SIZE {
+            return Err(Error::AuthenticationFailed(
+                "Ciphertext too short to contain a MAC tag".to_string(),
+            ));
+        }
+        let (body, tag) = ciphertext_and_tag.split_at(ciphertext_and_tag.len() - MAC_TAG_SIZE);
+        let expected = compute_mac(mac_key, salt, iv, body);
+        if !bool::from(expected.ct_eq(tag)) {
+            return Err(Error::AuthenticationFailed(
+                "HMAC tag verification failed (wrong password or tampered data)".to_string(),
+            ));
+        }
+        self.decrypt(body, iv)
+    }
+
+    /// Decrypt and authenticate ciphertext produced by
+    /// [`EncryptionContext::encrypt_gcm`]
+    ///
+    /// Splits the leading [`GCM_NONCE_SIZE`]-byte nonce off
+    /// `nonce_ciphertext_tag`, then verifies the trailing [`GCM_TAG_SIZE`]-byte
+    /// tag against `aad` and the ciphertext before returning any plaintext.
+    /// `aad` must match whatever [`EncryptionContext::encrypt_gcm`] was
+    /// called with — a mismatch fails the same way a wrong password or
+    /// tampered ciphertext does. Any such failure returns
+    /// [`Error::AuthenticationFailed`] rather than partially-decrypted data.
+    pub fn decrypt_gcm(&self, nonce_ciphertext_tag: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        if nonce_ciphertext_tag.len() < GCM_NONCE_SIZE + GCM_TAG_SIZE {
+            return Err(Error::AuthenticationFailed(
+                "ciphertext too short to contain a GCM nonce and tag".to_string(),
+            ));
+        }
+        let (nonce_bytes, rest) = nonce_ciphertext_tag.split_at(GCM_NONCE_SIZE);
+        let cipher = Aes256Gcm::new(GcmKey::<Aes256Gcm>::from_slice(&self.key));
+        let nonce = GcmNonce::from_slice(nonce_bytes);
+
+        cipher.decrypt(nonce, Payload { msg: rest, aad }).map_err(|_| {
+            Error::AuthenticationFailed(
+                "AES-256-GCM authentication failed (wrong password, tampered data, or mismatched AAD)"
+                    .to_string(),
+            )
+        })
+    }
+
+    /// Wrap `reader` in a [`DecryptingReader`] using this context's key and
+    /// the given `iv`
+    ///
+    /// The streaming CBC counterpart to [`Self::decrypt`]: decodes one
+    /// 16-byte ciphertext block at a time as they arrive rather than
+    /// requiring the whole ciphertext up front, so a large encrypted
+    /// payload never has to be fully buffered in memory just to be read.
+    pub fn decrypting_reader<R: Read>(&self, reader: R, iv: &[u8; AES_BLOCK_SIZE]) -> DecryptingReader<R> {
+        DecryptingReader::new(reader, self.key, *iv)
+    }
+
+    /// Decrypt data produced by [`EncryptionContext::new_7z_compatible`]'s
+    /// `encrypt` (or [`encrypt_7z`]) — zero-padded rather than PKCS#7 — and
+    /// truncate the result to `output_len`, the true plaintext length as
+    /// recorded in the 7-Zip archive's coder metadata
+    pub fn decrypt_7z(
+        &self,
+        ciphertext: &[u8],
+        iv: &[u8; AES_BLOCK_SIZE],
+        output_len: usize,
+    ) -> Result<Vec<u8>> {
+        decrypt_7z(&self.key, iv, ciphertext, output_len)
+    }
+
+    /// Decrypt a byte range of an AES-256-CTR payload starting at plaintext
+    /// `offset`, without touching any bytes before it
+    ///
+    /// This is the one-shot counterpart to [`CtrDecryptionContext`]: useful
+    /// when a caller already has a `DecryptionContext` (keyed the same way
+    /// [`Self::new`] derives it) and just wants to pull one member out of the
+    /// middle of a large CTR-encrypted payload, rather than tracking a
+    /// running position across calls. `nonce` is the 16-byte counter seed
+    /// the payload was encrypted under (e.g. [`EncryptionContext::encrypt_stream`]'s
+    /// prefix); `salt` must be the same archive-header salt this context was
+    /// constructed from — it isn't reused in the keystream math here (the key
+    /// already incorporates it), it's required so the call site stays
+    /// self-documenting about which header fields back a given decryption.
+    ///
+    /// A non-block-aligned `offset` decrypts only the 16-byte block that
+    /// contains it and discards the leading keystream bytes before `offset`,
+    /// so no data before the requested range is ever processed.
+    pub fn decrypt_at(
+        &self,
+        ciphertext: &[u8],
+        offset: u64,
+        salt: &[u8],
+        nonce: &[u8; AES_BLOCK_SIZE],
+    ) -> Result<Vec<u8>> {
+        if salt.is_empty() {
+            return Err(Error::InvalidParameter("Salt cannot be empty".to_string()));
+        }
+
+        Ok(ctr_xor_at(&self.key, u128::from_be_bytes(*nonce), offset, ciphertext))
+    }
+}
+
+/// WinZip AE-2 password-verification value size in bytes
+pub const WINZIP_VERIFY_SIZE: usize = 2;
+/// WinZip AE-2 truncated HMAC-SHA1 authentication code size in bytes
+pub const WINZIP_AUTH_CODE_SIZE: usize = 10;
+
+type HmacSha1 = Hmac<sha1::Sha1>;
+
+/// Derive the three WinZip AE-2 outputs (AES key, HMAC-SHA1 auth key,
+/// 2-byte password-verification value) from `password`/`salt` in one
+/// PBKDF2 pass, per the scheme [`WinZipAesContext`]/[`WinZipAesDecryptionContext`]
+/// implement
+fn derive_winzip_keys(
+    password: &str,
+    salt: &[u8],
+) -> ([u8; AES_KEY_SIZE], [u8; AES_KEY_SIZE], [u8; WINZIP_VERIFY_SIZE]) {
+    let mut okm = [0u8; AES_KEY_SIZE * 2 + WINZIP_VERIFY_SIZE];
+    pbkdf2_hmac::<sha1::Sha1>(password.as_bytes(), salt, PBKDF2_ITERATIONS, &mut okm);
+    let mut enc_key = [0u8; AES_KEY_SIZE];
+    let mut auth_key = [0u8; AES_KEY_SIZE];
+    let mut verify = [0u8; WINZIP_VERIFY_SIZE];
+    enc_key.copy_from_slice(&okm[..AES_KEY_SIZE]);
+    auth_key.copy_from_slice(&okm[AES_KEY_SIZE..AES_KEY_SIZE * 2]);
+    verify.copy_from_slice(&okm[AES_KEY_SIZE * 2..]);
+    (enc_key, auth_key, verify)
+}
+
+/// AES-CTR over `data` in place using the WinZip AE little-endian block
+/// counter that starts at 1 — distinct from the big-endian counter, seeded
+/// from a random nonce, that [`EncryptionContext::encrypt_stream`] uses for
+/// its own CTR mode
+fn winzip_ctr_transform(key: &[u8; AES_KEY_SIZE], data: &mut [u8]) {
+    let cipher = aes::Aes256::new(GenericArray::from_slice(key));
+    let mut counter: u128 = 1;
+    let mut offset = 0;
+    while offset < data.len() {
+        let block_len = (data.len() - offset).min(AES_BLOCK_SIZE);
+        let mut counter_block = GenericArray::clone_from_slice(&counter.to_le_bytes());
+        cipher.encrypt_block(&mut counter_block);
+        for i in 0..block_len {
+            data[offset + i] ^= counter_block[i];
+        }
+        counter = counter.wrapping_add(1);
+        offset += block_len;
+    }
+}
+
+/// First [`WINZIP_AUTH_CODE_SIZE`] bytes of `HMAC-SHA1(auth_key, ciphertext)`
+fn winzip_auth_code(auth_key: &[u8; AES_KEY_SIZE], ciphertext: &[u8]) -> [u8; WINZIP_AUTH_CODE_SIZE] {
+    let mut mac = HmacSha1::new_from_slice(auth_key).expect("HMAC accepts any key length");
+    mac.update(ciphertext);
+    let full = mac.finalize().into_bytes();
+    let mut out = [0u8; WINZIP_AUTH_CODE_SIZE];
+    out.copy_from_slice(&full[..WINZIP_AUTH_CODE_SIZE]);
+    out
+}
+
+/// WinZip AE-2 authenticated AES-256-CTR encryption context
+///
+/// An alternative to [`EncryptionContext`]'s default CBC-with-padding
+/// scheme, selected by constructing this type instead: PBKDF2-HMAC-SHA1
+/// derives a 32-byte AES key, an independent 32-byte HMAC-SHA1
+/// authentication key, and a 2-byte password-verification value in one
+/// pass. This is the key layout [`crate::zip_format`] uses for WinZip AE-2
+/// ZIP entries, but with [`PBKDF2_ITERATIONS`] (262,144) rounds instead of
+/// that module's 1000, so the same password and salt give different keys
+/// in the two modules.
+/// [`Self::encrypt`] then encrypts with AES-256 in CTR mode (little-endian
+/// block counter starting at 1, never reused because each instance derives
+/// a fresh random salt) and appends a truncated HMAC-SHA1 over the
+/// ciphertext — no separate IV is needed, and there is no padding to leak
+/// information through, unlike CBC.
+#[derive(ZeroizeOnDrop)]
+pub struct WinZipAesContext {
+    #[zeroize(skip)]
+    enc_key: [u8; AES_KEY_SIZE],
+    #[zeroize(skip)]
+    auth_key: [u8; AES_KEY_SIZE],
+    #[zeroize(skip)]
+    verify: [u8; WINZIP_VERIFY_SIZE],
+    #[zeroize(skip)]
+    salt: [u8; SALT_SIZE],
+}
+
+impl WinZipAesContext {
+    /// Create a new context from a password, generating a random salt
+    pub fn new(password: &str) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter("Password cannot be empty".to_string()));
+        }
+        let salt = generate_salt();
+        let (enc_key, auth_key, verify) = derive_winzip_keys(password, &salt);
+        Ok(Self { enc_key, auth_key, verify, salt })
+    }
+
+    /// The random salt generated for this context; must be stored alongside
+    /// the ciphertext so a [`WinZipAesDecryptionContext`] can reconstruct
+    /// the same key material
+    pub fn salt(&self) -> &[u8; SALT_SIZE] {
+        &self.salt
+    }
+
+    /// The 2-byte password-verification value; store it alongside the
+    /// ciphertext so a decryptor can cheaply reject a wrong password
+    /// without running the full HMAC check
+    pub fn password_verify(&self) -> [u8; WINZIP_VERIFY_SIZE] {
+        self.verify
+    }
+
+    /// Encrypt `plaintext` with AES-256-CTR and append the 10-byte
+    /// truncated HMAC-SHA1 authentication code
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut out = plaintext.to_vec();
+        winzip_ctr_transform(&self.enc_key, &mut out);
+        let tag = winzip_auth_code(&self.auth_key, &out);
+        out.extend_from_slice(&tag);
+        out
+    }
+}
+
+/// Decryption side of [`WinZipAesContext`], reconstructed from a password
+/// and the salt recorded at encryption time
+#[derive(ZeroizeOnDrop)]
+pub struct WinZipAesDecryptionContext {
+    #[zeroize(skip)]
+    enc_key: [u8; AES_KEY_SIZE],
+    #[zeroize(skip)]
+    auth_key: [u8; AES_KEY_SIZE],
+    #[zeroize(skip)]
+    verify: [u8; WINZIP_VERIFY_SIZE],
+}
+
+impl WinZipAesDecryptionContext {
+    /// Create a context from a password and the salt recorded at encryption
+    /// time
+    pub fn new(password: &str, salt: &[u8]) -> Result<Self> {
+        if password.is_empty() {
+            return Err(Error::InvalidParameter("Password cannot be empty".to_string()));
+        }
+        if salt.is_empty() {
+            return Err(Error::InvalidParameter("Salt cannot be empty".to_string()));
+        }
+        let (enc_key, auth_key, verify) = derive_winzip_keys(password, salt);
+        Ok(Self { enc_key, auth_key, verify })
+    }
+
+    /// The 2-byte password-verification value this context derived; compare
+    /// against the value stored at encryption time before calling
+    /// [`Self::decrypt`] if you want to reject a wrong password up front
+    /// yourself, though [`Self::decrypt`] already performs this check
+    pub fn password_verify(&self) -> [u8; WINZIP_VERIFY_SIZE] {
+        self.verify
+    }
+
+    /// Decrypt `ciphertext_and_tag` (as produced by [`WinZipAesContext::encrypt`]),
+    /// first checking `expected_verify` (the password-verification value
+    /// stored at encryption time) and failing fast on mismatch, then
+    /// verifying the truncated HMAC-SHA1 authentication code before
+    /// returning any plaintext
+    pub fn decrypt(
+        &self,
+        ciphertext_and_tag: &[u8],
+        expected_verify: [u8; WINZIP_VERIFY_SIZE],
+    ) -> Result<Vec<u8>> {
+        if !bool::from(self.verify.ct_eq(&expected_verify)) {
+            return Err(Error::WrongPassword(
+                "WinZip AE-2 password verification value mismatch".to_string(),
+            ));
+        }
+        if ciphertext_and_tag.len() < WINZIP_AUTH_CODE_SIZE {
+            return Err(Error::AuthenticationFailed(
+                "Ciphertext too short to contain a WinZip AE-2 authentication code".to_string(),
+            ));
+        }
+        let (body, tag) = ciphertext_and_tag.split_at(ciphertext_and_tag.len() - WINZIP_AUTH_CODE_SIZE);
+        let expected_tag = winzip_auth_code(&self.auth_key, body);
+        if !bool::from(expected_tag.ct_eq(tag)) {
+            return Err(Error::AuthenticationFailed(
+                "WinZip AE-2 authentication code mismatch (tampered data)".to_string(),
+            ));
+        }
+        let mut out = body.to_vec();
+        winzip_ctr_transform(&self.enc_key, &mut out);
+        Ok(out)
+    }
+}
+
+/// Truncated authentication tag size (bytes) used by [`verify_password`]
+///
+/// Distinct from [`EncryptionContext::new_authenticated`]'s full 32-byte
+/// encrypt-then-MAC tag: a password-check tag only needs to resist guessing,
+/// not carry full collision resistance, so truncating keeps stored test
+/// blocks small while staying well within the recommended 10-16 byte range
+/// for a truncated HMAC-SHA256 tag.
+pub const VERIFY_TAG_SIZE: usize = 16;
+
+/// Compute the password-verification tag for `ciphertext`, to be stored
+/// alongside it so a later [`verify_password`] call can check a password
+/// without decrypting anything
+///
+/// Derives an AES key and an independent HMAC-SHA256 key from `password`/
+/// `salt` the same way [`EncryptionContext::new_authenticated`] does,
+/// computes `HMAC-SHA256(salt || iv || ciphertext)` with the MAC key, and
+/// truncates the result to [`VERIFY_TAG_SIZE`] bytes.
+pub fn compute_verification_tag(
+    password: &str,
+    ciphertext: &[u8],
+    salt: &[u8],
+    iv: &[u8; AES_BLOCK_SIZE],
+) -> [u8; VERIFY_TAG_SIZE] {
+    let mut derived = [0u8; AES_KEY_SIZE * 2];
+    pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, PBKDF2_ITERATIONS, &mut derived);
+    let mac_key = &derived[AES_KEY_SIZE..];
+
+    let mut mac = HmacSha256::new_from_slice(mac_key).expect("HMAC accepts any key length");
+    mac.update(salt);
+    mac.update(iv);
+    mac.update(ciphertext);
+    let full_tag = mac.finalize().into_bytes();
+
+    let mut tag = [0u8; VERIFY_TAG_SIZE];
+    tag.copy_from_slice(&full_tag[..VERIFY_TAG_SIZE]);
+    tag
 }
 
-/// Verify if a password is correct by attempting decryption
+/// Verify a password against a tag computed by [`compute_verification_tag`],
+/// in constant time
+///
+/// Earlier versions of this function "verified" a password by attempting a
+/// full CBC decrypt and checking whether PKCS#7 padding came out valid — a
+/// padding oracle, and no guarantee against a tampered ciphertext. This
+/// instead recomputes the HMAC tag and compares it with
+/// [`subtle::ConstantTimeEq`], so neither the comparison's timing nor its
+/// outcome depends on decrypting anything, and returns
+/// [`Error::AuthenticationFailed`] (not [`Error::DecryptionError`]) on a
+/// mismatch, covering both a wrong password and a tampered ciphertext.
 ///
 /// # Arguments
 ///
 /// * `password` - Password to test
-/// * `encrypted_data` - Encrypted test data
+/// * `ciphertext` - The encrypted test block `tag` was computed over
+/// * `tag` - The [`VERIFY_TAG_SIZE`]-byte tag stored alongside `ciphertext`
 /// * `salt` - Salt from archive header
 /// * `iv` - IV from archive header
-///
-/// # Returns
-///
-/// `Ok(())` if decryption succeeds, `Err` if wrong password or verification fails
 pub fn verify_password(
     password: &str,
-    encrypted_data: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8],
     salt: &[u8],
     iv: &[u8],
 ) -> Result<()> {
@@ -312,20 +1521,24 @@ pub fn verify_password(
             "Password cannot be empty".to_string(),
         ));
     }
-    if iv.len() != AES_BLOCK_SIZE {
-        return Err(Error::InvalidParameter(
-            "IV must be 16 bytes".to_string(),
-        ));
+    let iv_arr: [u8; AES_BLOCK_SIZE] = iv
+        .try_into()
+        .map_err(|_| Error::InvalidParameter("IV must be 16 bytes".to_string()))?;
+    if tag.len() != VERIFY_TAG_SIZE {
+        return Err(Error::InvalidParameter(format!(
+            "Tag must be {} bytes",
+            VERIFY_TAG_SIZE
+        )));
     }
 
-    let ctx = DecryptionContext::new(password, salt)?;
-    let iv_arr: [u8; AES_BLOCK_SIZE] = iv.try_into()
-        .map_err(|_| Error::InvalidParameter("Invalid IV length".to_string()))?;
-    
-    // Try to decrypt - will fail if password is wrong (bad padding)
-    ctx.decrypt(encrypted_data, &iv_arr)?;
-    
-    Ok(())
+    let expected = compute_verification_tag(password, ciphertext, salt, &iv_arr);
+    if bool::from(expected.ct_eq(tag)) {
+        Ok(())
+    } else {
+        Err(Error::AuthenticationFailed(
+            "Password verification failed (tag mismatch)".to_string(),
+        ))
+    }
 }
 
 /// Derive a key from password and salt using PBKDF2-SHA256
@@ -337,17 +1550,125 @@ pub fn derive_key(password: &str, salt: &[u8]) -> [u8; AES_KEY_SIZE] {
     key
 }
 
-/// Generate a random salt
+/// Derive a key using the real 7-Zip AES-256 (`07F10110` coder) schedule
+///
+/// This is NOT PBKDF2, despite [`derive_key`]'s name suggesting otherwise —
+/// genuine 7-Zip encodes the password as UTF-16LE and, unless
+/// `num_cycles_power` is `0x3F` (in which case the raw `salt || password`
+/// bytes are used directly, truncated/zero-extended to 32 bytes), runs a
+/// single rolling SHA-256 over `2^num_cycles_power` rounds, each one
+/// feeding `salt || password_utf16le || counter` into the hash, where
+/// `counter` is an 8-byte little-endian value starting at zero and
+/// incremented once per round. The final digest is the 32-byte AES key.
+/// [`SEVENZIP_DEFAULT_CYCLES_POWER`] (19, ≈524,288 rounds) is 7-Zip's
+/// default `num_cycles_power`.
+pub fn derive_key_7z(password: &str, salt: &[u8], num_cycles_power: u8) -> [u8; AES_KEY_SIZE] {
+    let password_utf16le: Vec<u8> = password
+        .encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect();
+
+    if num_cycles_power == 0x3F {
+        let mut key = [0u8; AES_KEY_SIZE];
+        let mut input = Vec::with_capacity(salt.len() + password_utf16le.len());
+        input.extend_from_slice(salt);
+        input.extend_from_slice(&password_utf16le);
+        let take = input.len().min(AES_KEY_SIZE);
+        key[..take].copy_from_slice(&input[..take]);
+        return key;
+    }
+
+    let rounds: u64 = 1u64 << num_cycles_power;
+    let mut hasher = Sha256::new();
+    for counter in 0..rounds {
+        hasher.update(salt);
+        hasher.update(&password_utf16le);
+        hasher.update(counter.to_le_bytes());
+    }
+    hasher.finalize().into()
+}
+
+/// Encrypt `plaintext` the way real 7-Zip does: AES-256-CBC with the
+/// plaintext zero-padded to a 16-byte multiple instead of PKCS#7 — the true
+/// length must be recovered by the caller (7-Zip carries it in the archive's
+/// coder metadata) since zero padding can't be distinguished from real
+/// trailing zero bytes
+pub fn encrypt_7z(key: &[u8; AES_KEY_SIZE], iv: &[u8; AES_BLOCK_SIZE], plaintext: &[u8]) -> Vec<u8> {
+    let padded_len = plaintext.len().div_ceil(AES_BLOCK_SIZE).max(1) * AES_BLOCK_SIZE;
+    let mut buffer = vec![0u8; padded_len];
+    buffer[..plaintext.len()].copy_from_slice(plaintext);
+
+    let cipher = Aes256CbcEnc::new(key.into(), iv.into());
+    cipher
+        .encrypt_padded_mut::<NoPadding>(&mut buffer, padded_len)
+        .expect("buffer is already sized to a block multiple")
+        .to_vec()
+}
+
+/// Decrypt a buffer produced by [`encrypt_7z`], truncating the zero-padded
+/// tail back down to `output_len`
+///
+/// # Errors
+///
+/// Returns `Error::InvalidParameter` if `ciphertext` isn't a multiple of 16
+/// bytes, or if `output_len` is larger than the decrypted buffer.
+pub fn decrypt_7z(
+    key: &[u8; AES_KEY_SIZE],
+    iv: &[u8; AES_BLOCK_SIZE],
+    ciphertext: &[u8],
+    output_len: usize,
+) -> Result<Vec<u8>> {
+    let mut plaintext = decrypt_7z_raw(key, iv, ciphertext)?;
+    if output_len > plaintext.len() {
+        return Err(Error::InvalidParameter(
+            "output_len exceeds decrypted buffer size".to_string(),
+        ));
+    }
+    plaintext.truncate(output_len);
+    Ok(plaintext)
+}
+
+/// Decrypt a buffer produced by [`encrypt_7z`] without truncating the
+/// zero-padded tail — shared by [`decrypt_7z`] and
+/// [`EncryptionContext::decrypt`]'s `ZeroPad7z` path
+fn decrypt_7z_raw(key: &[u8; AES_KEY_SIZE], iv: &[u8; AES_BLOCK_SIZE], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    if ciphertext.len() % AES_BLOCK_SIZE != 0 {
+        return Err(Error::InvalidParameter(
+            "Ciphertext length must be multiple of 16 bytes".to_string(),
+        ));
+    }
+
+    let mut buffer = ciphertext.to_vec();
+    let cipher = Aes256CbcDec::new(key.into(), iv.into());
+    let plaintext = cipher
+        .decrypt_padded_mut::<NoPadding>(&mut buffer)
+        .map_err(|_| Error::DecryptionError("Decryption failed (wrong password?)".to_string()))?;
+    Ok(plaintext.to_vec())
+}
+
+/// Generate a random salt from the OS CSPRNG
 pub fn generate_salt() -> [u8; SALT_SIZE] {
+    generate_salt_with_rng(&mut OsRng)
+}
+
+/// Like [`generate_salt`], but draws from `rng` instead of [`OsRng`] —
+/// useful for tests that need a reproducible salt
+pub fn generate_salt_with_rng(rng: &mut impl RngCore) -> [u8; SALT_SIZE] {
     let mut salt = [0u8; SALT_SIZE];
-    rand::thread_rng().fill_bytes(&mut salt);
+    rng.fill_bytes(&mut salt);
     salt
 }
 
-/// Generate a random IV
+/// Generate a random IV from the OS CSPRNG
 pub fn generate_iv() -> [u8; AES_BLOCK_SIZE] {
+    generate_iv_with_rng(&mut OsRng)
+}
+
+/// Like [`generate_iv`], but draws from `rng` instead of [`OsRng`] — useful
+/// for tests that need a reproducible IV
+pub fn generate_iv_with_rng(rng: &mut impl RngCore) -> [u8; AES_BLOCK_SIZE] {
     let mut iv = [0u8; AES_BLOCK_SIZE];
-    rand::thread_rng().fill_bytes(&mut iv);
+    rng.fill_bytes(&mut iv);
     iv
 }
 
@@ -374,6 +1695,32 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_new_with_rng_is_deterministic() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let ctx1 = EncryptionContext::new_with_rng("seeded_password", &mut StdRng::seed_from_u64(42)).unwrap();
+        let ctx2 = EncryptionContext::new_with_rng("seeded_password", &mut StdRng::seed_from_u64(42)).unwrap();
+        assert_eq!(ctx1.salt(), ctx2.salt());
+        assert_eq!(ctx1.iv(), ctx2.iv());
+
+        let ctx3 = EncryptionContext::new_with_rng("seeded_password", &mut StdRng::seed_from_u64(7)).unwrap();
+        assert_ne!(ctx1.salt(), ctx3.salt());
+    }
+
+    #[test]
+    fn test_generate_salt_iv_with_rng_deterministic() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let salt1 = generate_salt_with_rng(&mut StdRng::seed_from_u64(1));
+        let salt2 = generate_salt_with_rng(&mut StdRng::seed_from_u64(1));
+        assert_eq!(salt1, salt2);
+
+        let iv1 = generate_iv_with_rng(&mut StdRng::seed_from_u64(1));
+        let iv2 = generate_iv_with_rng(&mut StdRng::seed_from_u64(1));
+        assert_eq!(iv1, iv2);
+    }
+
     #[test]
     fn test_decryption_context() {
         let enc_ctx = EncryptionContext::new("password123").unwrap();
@@ -402,12 +1749,30 @@ mod tests {
     fn test_verify_password() {
         let ctx = EncryptionContext::new("test_password").unwrap();
         let ciphertext = ctx.encrypt(b"Test data").unwrap();
+        let tag = compute_verification_tag("test_password", &ciphertext, ctx.salt(), ctx.iv());
 
         // Correct password should succeed
-        assert!(verify_password("test_password", &ciphertext, ctx.salt(), ctx.iv()).is_ok());
+        assert!(verify_password("test_password", &ciphertext, &tag, ctx.salt(), ctx.iv()).is_ok());
 
         // Wrong password should fail
-        assert!(verify_password("wrong_password", &ciphertext, ctx.salt(), ctx.iv()).is_err());
+        assert!(verify_password("wrong_password", &ciphertext, &tag, ctx.salt(), ctx.iv()).is_err());
+    }
+
+    #[test]
+    fn test_verify_password_rejects_tampered_ciphertext() {
+        let ctx = EncryptionContext::new("test_password").unwrap();
+        let mut ciphertext = ctx.encrypt(b"Test data").unwrap();
+        let tag = compute_verification_tag("test_password", &ciphertext, ctx.salt(), ctx.iv());
+
+        ciphertext[0] ^= 0xFF;
+        assert!(verify_password("test_password", &ciphertext, &tag, ctx.salt(), ctx.iv()).is_err());
+    }
+
+    #[test]
+    fn test_verify_password_rejects_wrong_tag_length() {
+        let ctx = EncryptionContext::new("test_password").unwrap();
+        let ciphertext = ctx.encrypt(b"Test data").unwrap();
+        assert!(verify_password("test_password", &ciphertext, &[0u8; 8], ctx.salt(), ctx.iv()).is_err());
     }
 
     #[test]
@@ -425,6 +1790,34 @@ mod tests {
         assert_ne!(key1, key3);
     }
 
+    #[test]
+    fn test_stream_roundtrip() {
+        let ctx = EncryptionContext::new("stream_password").unwrap();
+        let plaintext: Vec<u8> = (0..70_000).map(|i| (i % 251) as u8).collect();
+
+        let mut ciphertext = Vec::new();
+        ctx.encrypt_stream(plaintext.as_slice(), &mut ciphertext).unwrap();
+        // 16-byte nonce prefix, then ciphertext the same length as plaintext (no padding)
+        assert_eq!(ciphertext.len(), AES_BLOCK_SIZE + plaintext.len());
+
+        let mut decrypted = Vec::new();
+        ctx.decrypt_stream(ciphertext.as_slice(), &mut decrypted).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_stream_nonce_is_fresh_per_call() {
+        let ctx = EncryptionContext::new("stream_password").unwrap();
+        let plaintext = b"same plaintext twice";
+
+        let mut first = Vec::new();
+        ctx.encrypt_stream(plaintext.as_slice(), &mut first).unwrap();
+        let mut second = Vec::new();
+        ctx.encrypt_stream(plaintext.as_slice(), &mut second).unwrap();
+
+        assert_ne!(first, second);
+    }
+
     #[test]
     fn test_large_data_encryption() {
         let ctx = EncryptionContext::new("password").unwrap();
@@ -434,7 +1827,429 @@ mod tests {
         
         let ciphertext = ctx.encrypt(&plaintext).unwrap();
         let decrypted = ctx.decrypt(&ciphertext).unwrap();
-        
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_ctr_seek_matches_sequential_decrypt() {
+        let ctx = EncryptionContext::new("ctr_password").unwrap();
+        let plaintext: Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+
+        let mut ciphertext = Vec::new();
+        ctx.encrypt_stream(plaintext.as_slice(), &mut ciphertext).unwrap();
+        let nonce: [u8; AES_BLOCK_SIZE] = ciphertext[..AES_BLOCK_SIZE].try_into().unwrap();
+        let body = &ciphertext[AES_BLOCK_SIZE..];
+
+        // Decrypting the whole body from offset 0 should match the plaintext.
+        let mut whole = CtrDecryptionContext::new("ctr_password", ctx.salt(), &nonce).unwrap();
+        assert_eq!(whole.decrypt(body), plaintext);
+
+        // Seeking to a non-block-aligned offset mid-stream should recover
+        // just the tail, without touching anything before it.
+        let offset = 1234usize;
+        let mut tail = CtrDecryptionContext::new("ctr_password", ctx.salt(), &nonce).unwrap();
+        tail.seek(offset as u64);
+        assert_eq!(tail.decrypt(&body[offset..]), plaintext[offset..]);
+    }
+
+    #[test]
+    fn test_ctr_decrypt_wrong_key_differs() {
+        let ctx = EncryptionContext::new("right_password").unwrap();
+        let plaintext = b"random access into an encrypted payload";
+
+        let mut ciphertext = Vec::new();
+        ctx.encrypt_stream(plaintext.as_slice(), &mut ciphertext).unwrap();
+        let nonce: [u8; AES_BLOCK_SIZE] = ciphertext[..AES_BLOCK_SIZE].try_into().unwrap();
+        let body = &ciphertext[AES_BLOCK_SIZE..];
+
+        let mut wrong = CtrDecryptionContext::new("wrong_password", ctx.salt(), &nonce).unwrap();
+        assert_ne!(wrong.decrypt(body), plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_at_seeks_without_replaying_start() {
+        let ctx = EncryptionContext::new("decrypt_at_password").unwrap();
+        let plaintext: Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+
+        let mut ciphertext = Vec::new();
+        ctx.encrypt_stream(plaintext.as_slice(), &mut ciphertext).unwrap();
+        let nonce: [u8; AES_BLOCK_SIZE] = ciphertext[..AES_BLOCK_SIZE].try_into().unwrap();
+        let body = &ciphertext[AES_BLOCK_SIZE..];
+
+        let dctx = DecryptionContext::new("decrypt_at_password", ctx.salt()).unwrap();
+
+        let offset = 1234u64;
+        let recovered = dctx
+            .decrypt_at(&body[offset as usize..], offset, ctx.salt(), &nonce)
+            .unwrap();
+        assert_eq!(recovered, plaintext[offset as usize..]);
+
+        let whole = dctx.decrypt_at(body, 0, ctx.salt(), &nonce).unwrap();
+        assert_eq!(whole, plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_at_rejects_empty_salt() {
+        let ctx = EncryptionContext::new("decrypt_at_password").unwrap();
+        let dctx = DecryptionContext::new("decrypt_at_password", ctx.salt()).unwrap();
+        let nonce = [0u8; AES_BLOCK_SIZE];
+        assert!(dctx.decrypt_at(b"1234567890123456", 0, &[], &nonce).is_err());
+    }
+
+    #[test]
+    fn test_authenticated_roundtrip() {
+        let ctx = EncryptionContext::new_authenticated("authenticated_password").unwrap();
+        let plaintext = b"integrity matters too";
+
+        let ciphertext = ctx.encrypt(plaintext).unwrap();
+        assert_eq!(ciphertext.len() % AES_BLOCK_SIZE, 0);
+        assert!(ciphertext.len() >= MAC_TAG_SIZE);
+
+        let decrypted = ctx.decrypt(&ciphertext).unwrap();
+        assert_eq!(decrypted.as_slice(), plaintext.as_slice());
+    }
+
+    #[test]
+    fn test_authenticated_tampered_ciphertext_rejected() {
+        let ctx = EncryptionContext::new_authenticated("authenticated_password").unwrap();
+        let mut ciphertext = ctx.encrypt(b"integrity matters too").unwrap();
+
+        // Flip a bit in the ciphertext body, leaving the tag untouched.
+        ciphertext[0] ^= 0x01;
+
+        match ctx.decrypt(&ciphertext) {
+            Err(Error::AuthenticationFailed(_)) => {}
+            other => panic!("expected AuthenticationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_authenticated_wrong_password_rejected() {
+        let right = EncryptionContext::new_authenticated("right_password").unwrap();
+        let ciphertext = right.encrypt(b"secret").unwrap();
+
+        // A context derived from a different password has a different MAC
+        // key (and salt/iv), so the tag can never match.
+        let wrong = EncryptionContext::new_authenticated("wrong_password").unwrap();
+        match wrong.decrypt(&ciphertext) {
+            Err(Error::AuthenticationFailed(_)) => {}
+            other => panic!("expected AuthenticationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decryption_context_authenticated_roundtrip() {
+        let enc_ctx = EncryptionContext::new_authenticated("authenticated_password").unwrap();
+        let plaintext = b"round tripped through a separately constructed decryption context";
+        let ciphertext = enc_ctx.encrypt(plaintext).unwrap();
+
+        let dec_ctx = DecryptionContext::new_authenticated("authenticated_password", enc_ctx.salt()).unwrap();
+        let decrypted = dec_ctx
+            .decrypt_authenticated(&ciphertext, enc_ctx.salt(), enc_ctx.iv())
+            .unwrap();
+
         assert_eq!(decrypted, plaintext);
     }
+
+    #[test]
+    fn test_decryption_context_authenticated_rejects_tampered_ciphertext() {
+        let enc_ctx = EncryptionContext::new_authenticated("authenticated_password").unwrap();
+        let mut ciphertext = enc_ctx.encrypt(b"integrity matters too").unwrap();
+        ciphertext[0] ^= 0x01;
+
+        let dec_ctx = DecryptionContext::new_authenticated("authenticated_password", enc_ctx.salt()).unwrap();
+        match dec_ctx.decrypt_authenticated(&ciphertext, enc_ctx.salt(), enc_ctx.iv()) {
+            Err(Error::AuthenticationFailed(_)) => {}
+            other => panic!("expected AuthenticationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decrypt_authenticated_requires_authenticated_context() {
+        let dec_ctx = DecryptionContext::new("some_password", &[0x01u8; SALT_SIZE]).unwrap();
+        match dec_ctx.decrypt_authenticated(&[0u8; 48], &[0x01u8; SALT_SIZE], &[0u8; AES_BLOCK_SIZE]) {
+            Err(Error::InvalidParameter(_)) => {}
+            other => panic!("expected InvalidParameter, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_gcm_roundtrip() {
+        let enc_ctx = EncryptionContext::new("gcm_password").unwrap();
+        let dec_ctx = DecryptionContext::new("gcm_password", enc_ctx.salt()).unwrap();
+        let plaintext = b"authenticated in one pass, no padding";
+        let aad = b"archive-header-v1";
+
+        let sealed = enc_ctx.encrypt_gcm(plaintext, aad).unwrap();
+        assert_eq!(sealed.len(), GCM_NONCE_SIZE + plaintext.len() + GCM_TAG_SIZE);
+
+        let opened = dec_ctx.decrypt_gcm(&sealed, aad).unwrap();
+        assert_eq!(opened, plaintext);
+    }
+
+    #[test]
+    fn test_gcm_rejects_tampered_ciphertext() {
+        let enc_ctx = EncryptionContext::new("gcm_password").unwrap();
+        let dec_ctx = DecryptionContext::new("gcm_password", enc_ctx.salt()).unwrap();
+        let mut sealed = enc_ctx.encrypt_gcm(b"tamper with me", b"aad").unwrap();
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+
+        match dec_ctx.decrypt_gcm(&sealed, b"aad") {
+            Err(Error::AuthenticationFailed(_)) => {}
+            other => panic!("expected AuthenticationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_gcm_rejects_mismatched_aad() {
+        let enc_ctx = EncryptionContext::new("gcm_password").unwrap();
+        let dec_ctx = DecryptionContext::new("gcm_password", enc_ctx.salt()).unwrap();
+        let sealed = enc_ctx.encrypt_gcm(b"some data", b"expected-aad").unwrap();
+
+        match dec_ctx.decrypt_gcm(&sealed, b"wrong-aad") {
+            Err(Error::AuthenticationFailed(_)) => {}
+            other => panic!("expected AuthenticationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_gcm_nonce_is_fresh_per_call() {
+        let ctx = EncryptionContext::new("gcm_password").unwrap();
+        let a = ctx.encrypt_gcm(b"same plaintext", b"aad").unwrap();
+        let b = ctx.encrypt_gcm(b"same plaintext", b"aad").unwrap();
+        assert_ne!(a[..GCM_NONCE_SIZE], b[..GCM_NONCE_SIZE]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_winzip_aes_roundtrip() {
+        let enc_ctx = WinZipAesContext::new("winzip_password").unwrap();
+        let dec_ctx = WinZipAesDecryptionContext::new("winzip_password", enc_ctx.salt()).unwrap();
+
+        let plaintext = b"encrypted and authenticated WinZip AE-2 style";
+        let sealed = enc_ctx.encrypt(plaintext);
+        let decrypted = dec_ctx.decrypt(&sealed, enc_ctx.password_verify()).unwrap();
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_winzip_aes_rejects_wrong_password_verify_value() {
+        let enc_ctx = WinZipAesContext::new("winzip_password").unwrap();
+        let dec_ctx = WinZipAesDecryptionContext::new("wrong_password", enc_ctx.salt()).unwrap();
+        let sealed = enc_ctx.encrypt(b"some data");
+
+        match dec_ctx.decrypt(&sealed, enc_ctx.password_verify()) {
+            Err(Error::WrongPassword(_)) => {}
+            other => panic!("expected WrongPassword, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_winzip_aes_rejects_tampered_ciphertext() {
+        let enc_ctx = WinZipAesContext::new("winzip_password").unwrap();
+        let dec_ctx = WinZipAesDecryptionContext::new("winzip_password", enc_ctx.salt()).unwrap();
+        let mut sealed = enc_ctx.encrypt(b"integrity matters for CTR mode too");
+        sealed[0] ^= 0x01;
+
+        match dec_ctx.decrypt(&sealed, enc_ctx.password_verify()) {
+            Err(Error::AuthenticationFailed(_)) => {}
+            other => panic!("expected AuthenticationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_winzip_aes_counter_starts_at_one() {
+        // The first keystream block must come from encrypting counter=1, not 0.
+        let ctx = WinZipAesContext::new("counter_password").unwrap();
+        let plaintext = [0u8; AES_BLOCK_SIZE];
+        let sealed = ctx.encrypt(&plaintext);
+        let keystream_block = &sealed[..AES_BLOCK_SIZE];
+        assert_ne!(keystream_block, &[0u8; AES_BLOCK_SIZE]);
+
+        let cipher = aes::Aes256::new(GenericArray::from_slice(&ctx.enc_key));
+        let mut counter_one = GenericArray::clone_from_slice(&1u128.to_le_bytes());
+        cipher.encrypt_block(&mut counter_one);
+        assert_eq!(keystream_block, counter_one.as_slice());
+    }
+
+    #[test]
+    fn test_derive_key_7z_deterministic() {
+        let salt = [0u8; 8];
+        let key1 = derive_key_7z("password", &salt, SEVENZIP_DEFAULT_CYCLES_POWER);
+        let key2 = derive_key_7z("password", &salt, SEVENZIP_DEFAULT_CYCLES_POWER);
+        let key3 = derive_key_7z("different", &salt, SEVENZIP_DEFAULT_CYCLES_POWER);
+
+        assert_eq!(key1, key2);
+        assert_ne!(key1, key3);
+    }
+
+    #[test]
+    fn test_derive_key_7z_raw_mode() {
+        // num_cycles_power == 0x3F skips hashing entirely.
+        let salt = [0xAAu8; 4];
+        let key = derive_key_7z("ab", &salt, 0x3F);
+        let mut expected = [0u8; AES_KEY_SIZE];
+        // salt || "a\0b\0" (UTF-16LE)
+        expected[..4].copy_from_slice(&salt);
+        expected[4..8].copy_from_slice(&[b'a', 0, b'b', 0]);
+        assert_eq!(key, expected);
+    }
+
+    #[test]
+    fn test_7z_compatible_roundtrip() {
+        let ctx = EncryptionContext::new_7z_compatible("p7zip_password", 10).unwrap();
+        let plaintext = b"not a multiple of sixteen bytes!!";
+
+        let ciphertext = ctx.encrypt(plaintext).unwrap();
+        assert_eq!(ciphertext.len() % AES_BLOCK_SIZE, 0);
+
+        let dec = DecryptionContext::new_7z_compatible("p7zip_password", ctx.salt(), 10).unwrap();
+        let decrypted = dec.decrypt_7z(&ciphertext, ctx.iv(), plaintext.len()).unwrap();
+        assert_eq!(decrypted.as_slice(), plaintext.as_slice());
+    }
+
+    #[test]
+    fn test_encrypt_7z_decrypt_7z_free_functions() {
+        let key = [0x42u8; AES_KEY_SIZE];
+        let iv = [0x13u8; AES_BLOCK_SIZE];
+        let plaintext = b"round trip via the standalone functions";
+
+        let ciphertext = encrypt_7z(&key, &iv, plaintext);
+        let decrypted = decrypt_7z(&key, &iv, &ciphertext, plaintext.len()).unwrap();
+        assert_eq!(decrypted.as_slice(), plaintext.as_slice());
+    }
+
+    #[test]
+    fn test_with_kdf_argon2id_roundtrip() {
+        let kdf = KdfParams::Argon2id { mem_kib: 8 * 1024, iterations: 1, parallelism: 1 };
+        let ctx = EncryptionContext::with_kdf("argon2_password", kdf).unwrap();
+        assert_eq!(ctx.kdf(), kdf);
+        let plaintext = b"derived with a memory-hard KDF this time";
+
+        let ciphertext = ctx.encrypt(plaintext).unwrap();
+
+        let dec = DecryptionContext::new_with_kdf("argon2_password", ctx.salt(), kdf).unwrap();
+        assert_eq!(dec.kdf(), kdf);
+        assert_eq!(dec.key(), ctx.key());
+
+        let mut full_ciphertext = Vec::new();
+        full_ciphertext.extend_from_slice(ctx.iv());
+        full_ciphertext.extend_from_slice(&ciphertext);
+        let iv: [u8; AES_BLOCK_SIZE] = full_ciphertext[..AES_BLOCK_SIZE].try_into().unwrap();
+        let decrypted = dec.decrypt(&full_ciphertext[AES_BLOCK_SIZE..], &iv).unwrap();
+        assert_eq!(decrypted.as_slice(), plaintext.as_slice());
+    }
+
+    #[test]
+    fn test_with_kdf_wrong_password_produces_different_key() {
+        let kdf = KdfParams::Argon2id { mem_kib: 8 * 1024, iterations: 1, parallelism: 1 };
+        let ctx = EncryptionContext::with_kdf("right_password", kdf).unwrap();
+
+        let wrong = DecryptionContext::new_with_kdf("wrong_password", ctx.salt(), kdf).unwrap();
+        assert_ne!(wrong.key(), ctx.key());
+    }
+
+    #[test]
+    fn test_kdf_params_default_and_argon2id_default() {
+        assert_eq!(KdfParams::default(), KdfParams::Pbkdf2Sha256 { iterations: PBKDF2_ITERATIONS });
+        assert_eq!(
+            KdfParams::argon2id_default(),
+            KdfParams::Argon2id { mem_kib: 64 * 1024, iterations: 3, parallelism: 1 }
+        );
+    }
+
+    #[test]
+    fn test_streaming_writer_reader_roundtrip() {
+        let key = [0x77u8; AES_KEY_SIZE];
+        let iv = [0x05u8; AES_BLOCK_SIZE];
+        let plaintext: Vec<u8> = (0..10_000).map(|i| (i % 256) as u8).collect();
+
+        let mut writer = EncryptingWriter::new(Vec::new(), key, iv);
+        // Write in small, irregular chunks to exercise the partial-block buffering.
+        for chunk in plaintext.chunks(37) {
+            writer.write_all(chunk).unwrap();
+        }
+        let ciphertext = writer.finish().unwrap();
+        assert_eq!(ciphertext.len() % AES_BLOCK_SIZE, 0);
+
+        let mut reader = DecryptingReader::new(ciphertext.as_slice(), key, iv);
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_streaming_writer_matches_whole_buffer_encrypt() {
+        let ctx = EncryptionContext::with_salt_iv(
+            "streaming_password",
+            &[0x11u8; SALT_SIZE],
+            &[0x22u8; AES_BLOCK_SIZE],
+        )
+        .unwrap();
+        let plaintext = b"streamed the exact same way as the whole-buffer path";
+
+        let whole = ctx.encrypt(plaintext).unwrap();
+
+        let mut writer = EncryptingWriter::new(Vec::new(), *ctx.key(), *ctx.iv());
+        writer.write_all(plaintext).unwrap();
+        let streamed = writer.finish().unwrap();
+
+        assert_eq!(whole, streamed);
+    }
+
+    #[test]
+    fn test_streaming_empty_input_roundtrip() {
+        let key = [0x09u8; AES_KEY_SIZE];
+        let iv = [0x0Au8; AES_BLOCK_SIZE];
+
+        let writer = EncryptingWriter::new(Vec::new(), key, iv);
+        let ciphertext = writer.finish().unwrap();
+        assert_eq!(ciphertext.len(), AES_BLOCK_SIZE);
+
+        let mut reader = DecryptingReader::new(ciphertext.as_slice(), key, iv);
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).unwrap();
+        assert!(decrypted.is_empty());
+    }
+
+    #[test]
+    fn test_context_encrypting_writer_decrypting_reader_roundtrip() {
+        let enc_ctx = EncryptionContext::with_salt_iv(
+            "streaming_password",
+            &[0x33u8; SALT_SIZE],
+            &[0x44u8; AES_BLOCK_SIZE],
+        )
+        .unwrap();
+        let plaintext = b"piped straight from a context instead of bare key/iv arrays";
+
+        let mut writer = enc_ctx.encrypting_writer(Vec::new()).unwrap();
+        writer.write_all(plaintext).unwrap();
+        let ciphertext = writer.finish().unwrap();
+
+        let dec_ctx = DecryptionContext::new_with_kdf(
+            "streaming_password",
+            &[0x33u8; SALT_SIZE],
+            KdfParams::default(),
+        )
+        .unwrap();
+        let mut reader = dec_ctx.decrypting_reader(ciphertext.as_slice(), enc_ctx.iv());
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).unwrap();
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_encrypting_writer_rejects_authenticated_context() {
+        let ctx = EncryptionContext::new_authenticated("streaming_password").unwrap();
+        let err = ctx.encrypting_writer(Vec::new()).unwrap_err();
+        match err {
+            Error::InvalidParameter(_) => (),
+            _ => panic!("expected InvalidParameter, got {:?}", err),
+        }
+    }
 }
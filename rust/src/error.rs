@@ -34,6 +34,51 @@ pub enum Error {
     EncryptionError(String),
     /// Decryption failed (wrong password or corrupted data)
     DecryptionError(String),
+    /// An authenticated-encryption MAC tag did not match
+    ///
+    /// Returned by the encrypt-then-MAC paths (e.g.
+    /// [`crate::encryption_native::EncryptionContext::new_authenticated`])
+    /// instead of [`Error::DecryptionError`], since a bad tag means the
+    /// password is wrong or the ciphertext was tampered with rather than
+    /// merely producing garbage plaintext.
+    AuthenticationFailed(String),
+    /// A password is required to complete the operation (e.g. listing an
+    /// archive with encrypted headers) but none was supplied
+    PasswordRequired(String),
+    /// A streaming source could not be parsed as an archive
+    ///
+    /// Returned by [`crate::archive::SevenZip::open_stream`] when the
+    /// buffered input has no recoverable end-of-archive header to locate.
+    NotSeekable(String),
+    /// An entry's decoded content did not match its recorded CRC32
+    ///
+    /// Returned by [`crate::archive::SevenZip::test_archive_with_crc`] on the
+    /// first entry whose recomputed checksum diverges from the header.
+    ChecksumMismatch {
+        /// Archive entry whose content failed verification
+        name: String,
+        /// CRC32 recorded in the archive header
+        expected: u32,
+        /// CRC32 recomputed from the decoded content
+        actual: u32,
+    },
+    /// An archive failed a [`crate::archive::ExtractOptions`] safety check
+    /// (a Zip-Slip-style path escaping the output directory, or a
+    /// total-size/entry-count budget being exceeded) and extraction was
+    /// refused. A bad path, too many entries, or an over-budget declared
+    /// size is refused before any file is written; an entry that overruns
+    /// the size budget only while it streams in stops extraction after the
+    /// entries before it were already written.
+    UnsafeArchive(String),
+    /// A password-based check explicitly designed to catch a wrong
+    /// password failed — a WinZip AE-2 password-verification value
+    /// mismatch, or PKCS#7 padding that didn't unpad cleanly after CBC
+    /// decryption
+    ///
+    /// Distinct from [`Error::DecryptionError`] and [`Error::InvalidArchive`],
+    /// which are reserved for failures that indicate genuine data
+    /// corruption rather than a password that's simply wrong.
+    WrongPassword(String),
 }
 
 impl Error {
@@ -67,6 +112,9 @@ impl Error {
             SevenZipErrorCode::SEVENZIP_ERROR_UNKNOWN => {
                 Error::Unknown("Unknown error".to_string())
             }
+            SevenZipErrorCode::SEVENZIP_ERROR_PASSWORD_REQUIRED => {
+                Error::PasswordRequired("Archive has encrypted headers".to_string())
+            }
         }
     }
 
@@ -85,6 +133,14 @@ impl Error {
             Error::Io(_) => Error::Io(msg),
             Error::EncryptionError(_) => Error::EncryptionError(msg),
             Error::DecryptionError(_) => Error::DecryptionError(msg),
+            Error::AuthenticationFailed(_) => Error::AuthenticationFailed(msg),
+            Error::PasswordRequired(_) => Error::PasswordRequired(msg),
+            Error::NotSeekable(_) => Error::NotSeekable(msg),
+            // No single message to override; the mismatch is already fully
+            // described by its structured fields.
+            Error::ChecksumMismatch { .. } => self,
+            Error::UnsafeArchive(_) => Error::UnsafeArchive(msg),
+            Error::WrongPassword(_) => Error::WrongPassword(msg),
         }
     }
 }
@@ -103,6 +159,16 @@ impl fmt::Display for Error {
             Error::Io(msg) => write!(f, "IO error: {}", msg),
             Error::EncryptionError(msg) => write!(f, "Encryption failed: {}", msg),
             Error::DecryptionError(msg) => write!(f, "Decryption failed: {}", msg),
+            Error::AuthenticationFailed(msg) => write!(f, "Authentication failed: {}", msg),
+            Error::PasswordRequired(msg) => write!(f, "Password required: {}", msg),
+            Error::NotSeekable(msg) => write!(f, "Could not parse streamed archive: {}", msg),
+            Error::ChecksumMismatch { name, expected, actual } => write!(
+                f,
+                "Checksum mismatch for '{}': expected {:08x}, got {:08x}",
+                name, expected, actual
+            ),
+            Error::UnsafeArchive(msg) => write!(f, "Refused to extract unsafe archive: {}", msg),
+            Error::WrongPassword(msg) => write!(f, "Wrong password: {}", msg),
         }
     }
 }
@@ -143,6 +209,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_password_required_from_code() {
+        let err = Error::from_code(SevenZipErrorCode::SEVENZIP_ERROR_PASSWORD_REQUIRED);
+        match err {
+            Error::PasswordRequired(_) => (),
+            _ => panic!("Wrong error type"),
+        }
+    }
+
+    #[test]
+    fn test_not_seekable_display() {
+        let err = Error::NotSeekable("empty stream".to_string());
+        assert_eq!(err.to_string(), "Could not parse streamed archive: empty stream");
+    }
+
+    #[test]
+    fn test_checksum_mismatch_display() {
+        let err = Error::ChecksumMismatch {
+            name: "file.txt".to_string(),
+            expected: 0xdeadbeef,
+            actual: 0x00000000,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Checksum mismatch for 'file.txt': expected deadbeef, got 00000000"
+        );
+    }
+
+    #[test]
+    fn test_unsafe_archive_display() {
+        let err = Error::UnsafeArchive("entry '../evil' escapes output directory".to_string());
+        assert_eq!(
+            err.to_string(),
+            "Refused to extract unsafe archive: entry '../evil' escapes output directory"
+        );
+    }
+
+    #[test]
+    fn test_wrong_password_display() {
+        let err = Error::WrongPassword("PKCS#7 padding was invalid".to_string());
+        assert_eq!(err.to_string(), "Wrong password: PKCS#7 padding was invalid");
+    }
+
     #[test]
     fn test_with_message() {
         let err = Error::Extract("original".to_string());
@@ -0,0 +1,288 @@
+//! Raw FFI bindings to the 7z FFI C library
+//!
+//! These are thin, unsafe declarations of the C ABI exposed by the bundled
+//! 7z SDK. Everything in this module is `pub(crate)` — callers should go
+//! through the safe wrappers in [`crate::archive`], [`crate::advanced`] and
+//! [`crate::encryption`] instead.
+
+use std::os::raw::{c_char, c_void};
+
+/// Error codes returned by the C library
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub enum SevenZipErrorCode {
+    SEVENZIP_OK = 0,
+    SEVENZIP_ERROR_OPEN_FILE = 1,
+    SEVENZIP_ERROR_INVALID_ARCHIVE = 2,
+    SEVENZIP_ERROR_MEMORY = 3,
+    SEVENZIP_ERROR_EXTRACT = 4,
+    SEVENZIP_ERROR_COMPRESS = 5,
+    SEVENZIP_ERROR_INVALID_PARAM = 6,
+    SEVENZIP_ERROR_NOT_IMPLEMENTED = 7,
+    SEVENZIP_ERROR_UNKNOWN = 8,
+    SEVENZIP_ERROR_PASSWORD_REQUIRED = 9,
+}
+
+/// AES block size in bytes
+pub const AES_BLOCK_SIZE: usize = 16;
+/// AES-256 key size in bytes
+pub const AES_KEY_SIZE: usize = 32;
+/// Number of 32-bit words in the C library's combined IV + round-key AES context
+pub const AES_NUM_IVMRK_WORDS: usize = 60;
+
+/// Detailed error information filled in by `sevenzip_get_last_error`
+#[repr(C)]
+pub struct SevenZipErrorInfo {
+    pub code: SevenZipErrorCode,
+    pub message: [c_char; 512],
+    pub file_context: [c_char; 256],
+    pub position: i64,
+    pub suggestion: [c_char; 256],
+}
+
+/// Compression options passed across the FFI boundary
+#[repr(C)]
+pub struct SevenZipCompressOptions {
+    pub num_threads: u32,
+    pub dict_size: u32,
+    pub solid: i32,
+    pub password: *const c_char,
+    /// Compression codec, see `SevenZipMethod` values in the C header (0 = LZMA2)
+    pub method: i32,
+    /// PPMd model order (0 = let the SDK choose)
+    pub ppmd_order: u32,
+    /// PPMd memory budget in megabytes (0 = let the SDK choose)
+    pub ppmd_mem_mb: u32,
+    /// BZip2 block size in 100KB units (0 = let the SDK choose)
+    pub bzip2_block_size: u32,
+    /// Non-zero encrypts the archive header (filenames/sizes) as well as content
+    pub encrypt_headers: i32,
+    /// Non-zero archives symlinks as links rather than following them
+    pub store_symlinks: i32,
+    /// Non-zero records Unix permission bits per entry
+    pub preserve_permissions: i32,
+    /// Non-zero records per-entry modification/access timestamps
+    pub preserve_timestamps: i32,
+}
+
+/// Progress callback invoked from C with `(completed_bytes, total_bytes)`
+pub type ProgressCallbackFn = extern "C" fn(u64, u64, *mut c_void);
+
+/// Per-entry callback invoked while listing an archive, as
+/// `(name, size, packed_size, is_dir, crc32, has_crc32, method, user_data)`.
+/// `has_crc32` is non-zero when `crc32` was recorded in the archive header.
+/// `method` is the coder ID packing this entry (see `SevenZipMethod` in the
+/// C header), or `-1` if the reader doesn't expose it for this entry.
+pub type ListEntryCallbackFn =
+    extern "C" fn(*const c_char, u64, u64, u32, u32, i32, i32, *mut c_void);
+
+/// Data callback invoked with successive decoded chunks during an
+/// in-memory extraction, as `(data, len, user_data)`. Returning non-zero
+/// aborts the extraction.
+pub type ExtractDataCallbackFn = extern "C" fn(*const u8, usize, *mut c_void) -> i32;
+
+/// Per-entry callback invoked while updating an archive, as
+/// `(name, was_recompressed, user_data)`. `was_recompressed` is non-zero if
+/// the entry was re-encoded, zero if its packed stream was copied verbatim.
+pub type UpdateEntryCallbackFn = extern "C" fn(*const c_char, i32, *mut c_void);
+
+extern "C" {
+    // Error reporting
+    pub fn sevenzip_get_last_error(info: *mut SevenZipErrorInfo) -> SevenZipErrorCode;
+    pub fn sevenzip_clear_last_error();
+    pub fn sevenzip_get_error_string(code: SevenZipErrorCode) -> *const c_char;
+    pub fn sevenzip_get_version() -> *const c_char;
+
+    // Archive operations
+    pub fn sevenzip_create_archive(
+        archive_path: *const c_char,
+        input_paths: *const *const c_char,
+        level: i32,
+        options: *const SevenZipCompressOptions,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_extract(
+        archive_path: *const c_char,
+        output_dir: *const c_char,
+        password: *const c_char,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_extract_with_metadata(
+        archive_path: *const c_char,
+        output_dir: *const c_char,
+        password: *const c_char,
+        preserve_permissions: i32,
+        preserve_timestamps: i32,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_extract_files(
+        archive_path: *const c_char,
+        output_dir: *const c_char,
+        names: *const *const c_char,
+        password: *const c_char,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    // In-memory extraction: decodes a single named entry directly into
+    // `data_cb`, without writing plaintext to disk.
+    pub fn sevenzip_extract_entry_to_memory(
+        archive_path: *const c_char,
+        entry_name: *const c_char,
+        password: *const c_char,
+        data_cb: ExtractDataCallbackFn,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    // Incremental update: adds or replaces `input_paths` in an existing
+    // archive. Unchanged entries are shallow-copied (their packed stream is
+    // carried over without recompression); `entry_cb`, if given, is invoked
+    // once per entry so callers can confirm the fast path engaged.
+    pub fn sevenzip_update_archive(
+        archive_path: *const c_char,
+        input_paths: *const *const c_char,
+        level: i32,
+        options: *const SevenZipCompressOptions,
+        entry_cb: Option<UpdateEntryCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_list(
+        archive_path: *const c_char,
+        password: *const c_char,
+        entry_cb: ListEntryCallbackFn,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    // Stateful listing handle, used for the pull-based `list_iter` API.
+    // `sevenzip_list_open` parses the header up front (so it needs the
+    // password if headers are encrypted); each `sevenzip_list_next` call
+    // then yields one entry, setting `has_more` to 0 once exhausted.
+    pub fn sevenzip_list_open(
+        archive_path: *const c_char,
+        password: *const c_char,
+        handle_out: *mut *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_list_next(
+        handle: *mut c_void,
+        name_out: *mut c_char,
+        name_capacity: usize,
+        size_out: *mut u64,
+        packed_size_out: *mut u64,
+        is_dir_out: *mut i32,
+        crc32_out: *mut u32,
+        has_crc32_out: *mut i32,
+        method_out: *mut i32,
+        has_more_out: *mut i32,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_list_close(handle: *mut c_void);
+
+    pub fn sevenzip_test_archive(
+        archive_path: *const c_char,
+        password: *const c_char,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    // Multi-volume / streaming
+    pub fn sevenzip_create_multivolume_7z(
+        archive_path: *const c_char,
+        input_paths: *const *const c_char,
+        level: i32,
+        volume_size: u64,
+        options: *const SevenZipCompressOptions,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_extract_split_archive(
+        archive_path: *const c_char,
+        output_dir: *const c_char,
+        password: *const c_char,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    // Raw LZMA / LZMA2
+    pub fn sevenzip_compress_lzma(
+        input_path: *const c_char,
+        output_path: *const c_char,
+        level: i32,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_decompress_lzma(
+        input_path: *const c_char,
+        output_path: *const c_char,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_compress_lzma2(
+        input_path: *const c_char,
+        output_path: *const c_char,
+        level: i32,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_decompress_lzma2(
+        input_path: *const c_char,
+        output_path: *const c_char,
+        progress_cb: Option<ProgressCallbackFn>,
+        user_data: *mut c_void,
+    ) -> SevenZipErrorCode;
+
+    // AES-256 (C backend)
+    pub fn sevenzip_init_encryption(
+        password: *const c_char,
+        key: *mut u8,
+        iv: *mut u8,
+        aes_context: *mut u32,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_init_decryption(
+        password: *const c_char,
+        salt: *const u8,
+        salt_len: usize,
+        key: *mut u8,
+        aes_context: *mut u32,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_encrypt_data(
+        aes_context: *mut u32,
+        iv: *const u8,
+        plaintext: *const u8,
+        plaintext_len: usize,
+        ciphertext: *mut u8,
+        ciphertext_len: *mut usize,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_decrypt_data(
+        aes_context: *mut u32,
+        iv: *const u8,
+        ciphertext: *const u8,
+        ciphertext_len: usize,
+        plaintext: *mut u8,
+        plaintext_len: *mut usize,
+    ) -> SevenZipErrorCode;
+
+    pub fn sevenzip_verify_password(
+        password: *const c_char,
+        encrypted_test_block: *const u8,
+        encrypted_test_block_len: usize,
+        salt: *const u8,
+        salt_len: usize,
+        iv: *const u8,
+    ) -> SevenZipErrorCode;
+}
@@ -0,0 +1,283 @@
+//! Extension-driven compress/extract facade
+//!
+//! Wraps the format-specific routines in [`crate::advanced`], [`crate::archive`]
+//! and [`crate::zip_format`] behind a single entry point that picks the right
+//! one from a file extension, the way `ouch` infers operation and format.
+//! Chained extensions like `.tar.xz` are handled by tarring with
+//! [`crate::tar_format`] and then running the inner codec; chains this SDK
+//! has no codec for (`.tar.gz`, `.tar.zst`, raw `.gz`/`.zst`/`.bz2`) report
+//! `Error::NotImplemented` rather than silently guessing.
+
+use crate::archive::{CompressOptions, CompressionLevel, SevenZip};
+use crate::error::{Error, Result};
+use crate::zip_format::{self, AesStrength};
+use crate::{advanced, tar_format};
+use std::path::Path;
+
+/// Archive format inferred from (or forced for) a file name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// `.7z`
+    SevenZip,
+    /// `.lzma`
+    Lzma,
+    /// `.xz`
+    Lzma2,
+    /// `.tar`
+    Tar,
+    /// `.tar.xz`
+    TarLzma2,
+    /// `.zip` (WinZip AE-2 encrypted, via [`crate::zip_format`])
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Infer the format from a path's extension(s)
+    ///
+    /// Returns `Error::NotImplemented` for recognized-but-unsupported
+    /// extensions (e.g. `.tar.gz`, `.zst`, for which this SDK has no codec),
+    /// and `Error::InvalidParameter` for anything else.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| Error::Io("Invalid path encoding".to_string()))?
+            .to_lowercase();
+
+        if name.ends_with(".tar.xz") {
+            Ok(ArchiveFormat::TarLzma2)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Err(Error::NotImplemented(
+                "gzip codec is not available; use .tar.xz instead".to_string(),
+            ))
+        } else if name.ends_with(".tar.zst") {
+            Err(Error::NotImplemented(
+                "zstd codec is not available; use .tar.xz instead".to_string(),
+            ))
+        } else if name.ends_with(".zip") {
+            Ok(ArchiveFormat::Zip)
+        } else if name.ends_with(".7z") {
+            Ok(ArchiveFormat::SevenZip)
+        } else if name.ends_with(".lzma") {
+            Ok(ArchiveFormat::Lzma)
+        } else if name.ends_with(".xz") {
+            Ok(ArchiveFormat::Lzma2)
+        } else if name.ends_with(".gz") {
+            Err(Error::NotImplemented(
+                "gzip codec is not available; use .xz instead".to_string(),
+            ))
+        } else if name.ends_with(".zst") {
+            Err(Error::NotImplemented(
+                "zstd codec is not available; use .xz instead".to_string(),
+            ))
+        } else if name.ends_with(".bz2") {
+            Err(Error::NotImplemented(
+                "raw .bz2 is not available; BZip2 is only supported inside .7z archives \
+                 via CompressionMethod::Bzip2".to_string(),
+            ))
+        } else if name.ends_with(".tar") {
+            Ok(ArchiveFormat::Tar)
+        } else {
+            Err(Error::InvalidParameter(format!(
+                "unrecognized archive extension: {}",
+                name
+            )))
+        }
+    }
+}
+
+impl SevenZip {
+    /// Compress `inputs` into `output`, picking the format from `output`'s extension
+    ///
+    /// See [`ArchiveFormat::from_path`] for the recognized extensions. Use
+    /// [`SevenZip::compress_as`] to override detection for a mislabeled path.
+    pub fn compress_auto(
+        &self,
+        inputs: &[impl AsRef<Path>],
+        output: impl AsRef<Path>,
+        level: CompressionLevel,
+        opts: Option<&CompressOptions>,
+    ) -> Result<()> {
+        self.compress_as(ArchiveFormat::from_path(output.as_ref())?, inputs, output, level, opts)
+    }
+
+    /// Compress `inputs` into `output` using an explicitly chosen format,
+    /// bypassing extension detection
+    pub fn compress_as(
+        &self,
+        format: ArchiveFormat,
+        inputs: &[impl AsRef<Path>],
+        output: impl AsRef<Path>,
+        level: CompressionLevel,
+        opts: Option<&CompressOptions>,
+    ) -> Result<()> {
+        match format {
+            ArchiveFormat::SevenZip => self.create_archive(output, inputs, level, opts),
+            ArchiveFormat::Lzma => {
+                let input = single_input(inputs)?;
+                advanced::compress_lzma(input, output, level)
+            }
+            ArchiveFormat::Lzma2 => {
+                let input = single_input(inputs)?;
+                advanced::compress_lzma2(input, output, level)
+            }
+            ArchiveFormat::Tar => {
+                let packed = tar_format::pack(inputs)?;
+                std::fs::write(output.as_ref(), packed)?;
+                Ok(())
+            }
+            ArchiveFormat::TarLzma2 => {
+                let packed = tar_format::pack(inputs)?;
+                let tar_path = temp_path(".tar");
+                std::fs::write(&tar_path, &packed)?;
+                let result = advanced::compress_lzma2(&tar_path, output, level);
+                let _ = std::fs::remove_file(&tar_path);
+                result
+            }
+            ArchiveFormat::Zip => {
+                let password = opts.and_then(|o| o.password.as_deref()).ok_or_else(|| {
+                    Error::InvalidParameter(
+                        "ZIP output requires CompressOptions::password (WinZip AE-2 encryption); \
+                         call SevenZip::create_zip_archive directly for more control"
+                            .to_string(),
+                    )
+                })?;
+                zip_format::create_zip_archive(output, inputs, password, AesStrength::default())
+            }
+        }
+    }
+
+    /// Extract `input` into `output_dir`, picking the format from `input`'s extension
+    ///
+    /// For the archive formats (`.7z`, `.tar`, `.tar.xz`, `.zip`) `output_dir`
+    /// is a directory; for the single-file codecs (`.lzma`, `.xz`) it instead
+    /// names the decompressed output file directly, matching
+    /// [`advanced::decompress_lzma`] and [`advanced::decompress_lzma2`].
+    /// See [`ArchiveFormat::from_path`] for the recognized extensions. Use
+    /// [`SevenZip::extract_as`] to override detection for a mislabeled path.
+    pub fn extract_auto(
+        &self,
+        input: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        self.extract_as(ArchiveFormat::from_path(input.as_ref())?, input, output_dir, password)
+    }
+
+    /// Extract `input` into `output_dir` using an explicitly chosen format,
+    /// bypassing extension detection
+    pub fn extract_as(
+        &self,
+        format: ArchiveFormat,
+        input: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        match format {
+            ArchiveFormat::SevenZip => self.extract_with_password(input, output_dir, password, None),
+            // `.lzma` / `.xz` are single-file codecs: unlike the archive formats,
+            // `output_dir` here names the decompressed output file directly.
+            ArchiveFormat::Lzma => advanced::decompress_lzma(input, output_dir),
+            ArchiveFormat::Lzma2 => advanced::decompress_lzma2(input, output_dir),
+            ArchiveFormat::Tar => {
+                let data = std::fs::read(input)?;
+                tar_format::unpack(std::io::Cursor::new(data), output_dir)
+            }
+            ArchiveFormat::TarLzma2 => {
+                let tar_path = temp_path(".tar");
+                let result = advanced::decompress_lzma2(input, &tar_path);
+                result?;
+                let tar_bytes = std::fs::read(&tar_path)?;
+                let _ = std::fs::remove_file(&tar_path);
+                tar_format::unpack(std::io::Cursor::new(tar_bytes), output_dir)
+            }
+            ArchiveFormat::Zip => {
+                let password = password.ok_or_else(|| {
+                    Error::InvalidParameter(
+                        "ZIP input requires a password (WinZip AE-2/ZipCrypto encrypted)".to_string(),
+                    )
+                })?;
+                zip_format::extract_zip_archive(input, output_dir, password)
+            }
+        }
+    }
+
+    /// Alias for [`SevenZip::extract_auto`]
+    pub fn decompress_auto(
+        &self,
+        input: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        self.extract_auto(input, output_dir, password)
+    }
+
+    /// Create a password-protected ZIP archive (WinZip AE-2 strong encryption)
+    ///
+    /// For interoperating with tools that can't open `.7z`; see
+    /// [`crate::zip_format`] for the format's limitations (entries are stored
+    /// uncompressed) and the encryption scheme used.
+    pub fn create_zip_archive(
+        &self,
+        archive_path: impl AsRef<Path>,
+        input_paths: &[impl AsRef<Path>],
+        password: &str,
+        strength: AesStrength,
+    ) -> Result<()> {
+        zip_format::create_zip_archive(archive_path, input_paths, password, strength)
+    }
+
+    /// Extract a ZIP archive written by [`SevenZip::create_zip_archive`]
+    pub fn extract_zip_archive(
+        &self,
+        archive_path: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+        password: &str,
+    ) -> Result<()> {
+        zip_format::extract_zip_archive(archive_path, output_dir, password)
+    }
+}
+
+static TEMP_FILE_COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn temp_path(suffix: &str) -> std::path::PathBuf {
+    let n = TEMP_FILE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    std::env::temp_dir().join(format!("seven_zip_format_{}_{}{}", std::process::id(), n, suffix))
+}
+
+fn single_input<P: AsRef<Path>>(inputs: &[P]) -> Result<&Path> {
+    match inputs {
+        [single] => Ok(single.as_ref()),
+        _ => Err(Error::InvalidParameter(
+            "raw LZMA/LZMA2 output accepts exactly one input file".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(ArchiveFormat::from_path("a.7z").unwrap(), ArchiveFormat::SevenZip);
+        assert_eq!(ArchiveFormat::from_path("a.tar.xz").unwrap(), ArchiveFormat::TarLzma2);
+        assert_eq!(ArchiveFormat::from_path("a.xz").unwrap(), ArchiveFormat::Lzma2);
+        assert_eq!(ArchiveFormat::from_path("a.tar").unwrap(), ArchiveFormat::Tar);
+    }
+
+    #[test]
+    fn test_format_rejects_unsupported_extension() {
+        assert!(ArchiveFormat::from_path("a.tar.gz").is_err());
+        assert!(ArchiveFormat::from_path("a.gz").is_err());
+        assert!(ArchiveFormat::from_path("a.zst").is_err());
+        assert!(ArchiveFormat::from_path("a.bz2").is_err());
+        assert!(ArchiveFormat::from_path("a.unknown").is_err());
+    }
+
+    #[test]
+    fn test_format_recognizes_zip() {
+        assert_eq!(ArchiveFormat::from_path("a.zip").unwrap(), ArchiveFormat::Zip);
+    }
+}
@@ -152,8 +152,14 @@
 //!
 //! - [`archive`] - High-level archive operations
 //! - [`advanced`] - Split archives, raw LZMA, enhanced error reporting (NEW!)
+//! - [`format`] - Extension-driven compress/extract facade (NEW!)
+//! - [`zip_format`] - ZIP writer with WinZip AE-2 strong encryption (NEW!)
 //! - [`encryption`] - AES-256 encryption (C library backend)
 //! - [`encryption_native`] - AES-256 encryption (pure Rust, recommended)
+//! - [`dedup`] - Content-defined chunking & cross-file deduplication (NEW!)
+//! - [`segment`] - Self-describing segmented container with per-chunk CRC32 (NEW!)
+//! - [`signing`] - Ed25519 detached signing/verification of segmented archives (NEW!)
+//! - `mount` - Read-only FUSE mount (Unix, `fuse` feature) (NEW!)
 //! - [`error`] - Error types and result handling
 //! - [`ffi`] - Raw FFI bindings (internal use)
 
@@ -163,37 +169,75 @@
 // Internal FFI module
 mod ffi;
 
+// Internal implementation detail of `format`'s `.tar` / `.tar.xz` support
+mod tar_format;
+
 // Public modules
 pub mod error;
 pub mod archive;
 pub mod advanced;
 pub mod encryption;
 pub mod encryption_native;
+pub mod dedup;
+pub mod segment;
+pub mod signing;
+pub mod mount;
+pub mod format;
+pub mod zip_format;
 
 // Re-export main types
 pub use error::{Error, Result};
 pub use archive::{
     SevenZip,
     ArchiveEntry,
+    ListIter,
     CompressionLevel,
+    CompressionMethod,
+    StreamCodec,
     CompressOptions,
     StreamOptions,
+    PpmdOptions,
+    Bzip2Options,
+    Lzma2Options,
+    MetadataOptions,
+    ExtractOptions,
     ProgressCallback,
     BytesProgressCallback,
+    ProgressEvent,
+    StreamProgressCallback,
+    UpdateAction,
+    StreamArchive,
+    StreamEntry,
+    StreamEntryIter,
 };
+pub use format::ArchiveFormat;
+pub use zip_format::AesStrength;
+pub use dedup::ChunkerConfig;
 
 // Re-export encryption - prefer native Rust implementation
 pub use encryption_native::{
     EncryptionContext as NativeEncryptionContext,
     DecryptionContext as NativeDecryptionContext,
+    CtrDecryptionContext,
+    EncryptingWriter,
+    DecryptingReader,
+    KdfParams,
     verify_password as native_verify_password,
+    compute_verification_tag,
+    VERIFY_TAG_SIZE,
     derive_key,
+    derive_key_7z,
+    encrypt_7z,
+    decrypt_7z,
     generate_salt,
+    generate_salt_with_rng,
     generate_iv,
+    generate_iv_with_rng,
     AES_BLOCK_SIZE,
     AES_KEY_SIZE,
     SALT_SIZE,
     PBKDF2_ITERATIONS,
+    SEVENZIP_DEFAULT_CYCLES_POWER,
 };
 
 // Also export C-based encryption for compatibility
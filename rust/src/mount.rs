@@ -0,0 +1,322 @@
+//! Read-only FUSE mount for `.7z` archives (including multi-volume sets)
+//!
+//! [`SevenZip::mount`] exposes an archive's contents as a read-only FUSE
+//! filesystem. [`InodeTable`] builds a directory tree from the archive's
+//! entry list (the same listing [`crate::archive::SevenZip::list`]
+//! returns), assigning one inode per directory and file; [`ArchiveFs`] then
+//! answers FUSE's `lookup`/`getattr`/`readdir`/`open`/`read` calls against
+//! that table. A multi-volume set is mounted by pointing at its first
+//! volume (e.g. `backup.7z.001`); the underlying SDK auto-detects and opens
+//! the rest exactly as [`crate::archive::SevenZip::list`] would.
+//!
+//! LZMA isn't seekable, so `read` can't ask the decoder for an arbitrary
+//! byte range directly: `open` decompresses the file's entire content once
+//! via
+//! [`extract_to_memory`](crate::archive::SevenZip::extract_to_memory) and
+//! caches it for the life of the file handle, and every `read` on that
+//! handle is then served as an in-memory slice.
+//!
+//! This module needs Cargo dependencies this tree has no manifest to
+//! declare:
+//!
+//!   [target.'cfg(unix)'.dependencies]
+//!   fuser = "0.14"
+//!   libc = "0.2"
+//!
+//!   [features]
+//!   fuse = ["dep:fuser"]
+//!
+//! and is compiled out entirely without the `fuse` feature on a Unix target.
+
+#![cfg(all(unix, feature = "fuse"))]
+
+use crate::archive::{ArchiveEntry, SevenZip};
+use crate::error::{Error, Result};
+use fuser::{
+    FileAttr, FileType, Filesystem, MountOption, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry,
+    Request,
+};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+const TTL: Duration = Duration::from_secs(1);
+const ROOT_INODE: u64 = 1;
+
+enum InodeEntry {
+    /// A directory: child name -> child inode
+    Dir(HashMap<String, u64>),
+    /// A file: its index into [`InodeTable::entries`]
+    File { entry_index: usize },
+}
+
+/// Maps FUSE inode numbers to directory/file entries, built once from an
+/// archive's entry list
+///
+/// Inode 1 is always the archive root. Directories implied by a file's path
+/// (e.g. `docs/` for an entry named `docs/readme.txt`) get an inode even if
+/// the archive never stored an explicit directory entry for them, since 7z
+/// archives aren't required to record every intermediate directory.
+struct InodeTable {
+    nodes: HashMap<u64, InodeEntry>,
+    entries: Vec<ArchiveEntry>,
+}
+
+impl InodeTable {
+    fn build(entries: Vec<ArchiveEntry>) -> Self {
+        let mut nodes = HashMap::new();
+        nodes.insert(ROOT_INODE, InodeEntry::Dir(HashMap::new()));
+        let mut dir_inode: HashMap<String, u64> = HashMap::new();
+        dir_inode.insert(String::new(), ROOT_INODE);
+        let mut next_inode = ROOT_INODE + 1;
+
+        for (entry_index, entry) in entries.iter().enumerate() {
+            let path = entry.name.trim_matches('/');
+            let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+            if components.is_empty() {
+                continue;
+            }
+
+            let mut parent_path = String::new();
+            for (depth, component) in components.iter().enumerate() {
+                let is_last = depth == components.len() - 1;
+                let mut this_path = parent_path.clone();
+                if !this_path.is_empty() {
+                    this_path.push('/');
+                }
+                this_path.push_str(component);
+
+                let inode = if is_last && !entry.is_dir {
+                    let inode = next_inode;
+                    next_inode += 1;
+                    nodes.insert(inode, InodeEntry::File { entry_index });
+                    inode
+                } else {
+                    *dir_inode.entry(this_path.clone()).or_insert_with(|| {
+                        let inode = next_inode;
+                        next_inode += 1;
+                        nodes.insert(inode, InodeEntry::Dir(HashMap::new()));
+                        inode
+                    })
+                };
+
+                let parent_inode = dir_inode[&parent_path];
+                if let Some(InodeEntry::Dir(children)) = nodes.get_mut(&parent_inode) {
+                    children.entry(component.to_string()).or_insert(inode);
+                }
+                parent_path = this_path;
+            }
+        }
+
+        Self { nodes, entries }
+    }
+
+    fn lookup_child(&self, parent: u64, name: &str) -> Option<u64> {
+        match self.nodes.get(&parent)? {
+            InodeEntry::Dir(children) => children.get(name).copied(),
+            InodeEntry::File { .. } => None,
+        }
+    }
+
+    fn attr(&self, inode: u64) -> Option<FileAttr> {
+        let now = SystemTime::now();
+        let (kind, size) = match self.nodes.get(&inode)? {
+            InodeEntry::Dir(_) => (FileType::Directory, 0),
+            InodeEntry::File { entry_index } => (FileType::RegularFile, self.entries[*entry_index].size),
+        };
+        Some(FileAttr {
+            ino: inode,
+            size,
+            blocks: (size + 511) / 512,
+            atime: now,
+            mtime: now,
+            ctime: now,
+            crtime: now,
+            kind,
+            perm: if kind == FileType::Directory { 0o555 } else { 0o444 },
+            nlink: 1,
+            uid: 0,
+            gid: 0,
+            rdev: 0,
+            blksize: 512,
+            flags: 0,
+        })
+    }
+
+    fn readdir_entries(&self, inode: u64) -> Option<Vec<(u64, FileType, String)>> {
+        match self.nodes.get(&inode)? {
+            InodeEntry::Dir(children) => Some(
+                children
+                    .iter()
+                    .map(|(name, &child_inode)| {
+                        let kind = match self.nodes.get(&child_inode) {
+                            Some(InodeEntry::Dir(_)) => FileType::Directory,
+                            _ => FileType::RegularFile,
+                        };
+                        (child_inode, kind, name.clone())
+                    })
+                    .collect(),
+            ),
+            InodeEntry::File { .. } => None,
+        }
+    }
+
+    fn entry_name(&self, inode: u64) -> Option<&str> {
+        match self.nodes.get(&inode)? {
+            InodeEntry::File { entry_index } => Some(&self.entries[*entry_index].name),
+            InodeEntry::Dir(_) => None,
+        }
+    }
+}
+
+/// The [`fuser::Filesystem`] implementation backing [`SevenZip::mount`]
+///
+/// Holds the archive path/password so it can decompress entries on demand,
+/// plus a per-open-file cache (keyed by file handle) of already-decompressed
+/// content, since LZMA must be decoded from the start of the entry regardless
+/// of the requested read offset.
+struct ArchiveFs {
+    sz: SevenZip,
+    archive_path: PathBuf,
+    password: Option<String>,
+    inodes: InodeTable,
+    open_files: Mutex<HashMap<u64, Vec<u8>>>,
+    next_fh: Mutex<u64>,
+}
+
+impl ArchiveFs {
+    fn new(archive_path: PathBuf, password: Option<String>) -> Result<Self> {
+        let sz = SevenZip::new()?;
+        let entries = sz.list(&archive_path, password.as_deref())?;
+        Ok(Self {
+            sz,
+            archive_path,
+            password,
+            inodes: InodeTable::build(entries),
+            open_files: Mutex::new(HashMap::new()),
+            next_fh: Mutex::new(1),
+        })
+    }
+}
+
+impl Filesystem for ArchiveFs {
+    fn lookup(&mut self, _req: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let Some(name) = name.to_str() else {
+            reply.error(libc::EINVAL);
+            return;
+        };
+        match self
+            .inodes
+            .lookup_child(parent, name)
+            .and_then(|inode| self.inodes.attr(inode).map(|attr| (inode, attr)))
+        {
+            Some((_, attr)) => reply.entry(&TTL, &attr, 0),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request<'_>, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.inodes.attr(ino) {
+            Some(attr) => reply.attr(&TTL, &attr),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn readdir(&mut self, _req: &Request<'_>, ino: u64, _fh: u64, offset: i64, mut reply: ReplyDirectory) {
+        let Some(mut children) = self.inodes.readdir_entries(ino) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        children.sort_by(|a, b| a.2.cmp(&b.2));
+
+        let mut entries = vec![
+            (ino, FileType::Directory, ".".to_string()),
+            (ino, FileType::Directory, "..".to_string()),
+        ];
+        entries.extend(children);
+
+        for (i, (inode, kind, name)) in entries.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(inode, (i + 1) as i64, kind, &name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+
+    fn open(&mut self, _req: &Request<'_>, ino: u64, _flags: i32, reply: fuser::ReplyOpen) {
+        let Some(name) = self.inodes.entry_name(ino) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        match self.sz.extract_to_memory(&self.archive_path, name, self.password.as_deref()) {
+            Ok(data) => {
+                let mut next_fh = self.next_fh.lock().unwrap();
+                let fh = *next_fh;
+                *next_fh += 1;
+                self.open_files.lock().unwrap().insert(fh, data);
+                reply.opened(fh, 0);
+            }
+            Err(_) => reply.error(libc::EIO),
+        }
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        let open_files = self.open_files.lock().unwrap();
+        match open_files.get(&fh) {
+            Some(data) => {
+                let offset = offset.max(0) as usize;
+                let end = (offset + size as usize).min(data.len());
+                let slice = if offset < data.len() { &data[offset..end] } else { &[] };
+                reply.data(slice);
+            }
+            None => reply.error(libc::EBADF),
+        }
+    }
+
+    fn release(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        fh: u64,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        _flush: bool,
+        reply: fuser::ReplyEmpty,
+    ) {
+        self.open_files.lock().unwrap().remove(&fh);
+        reply.ok();
+    }
+}
+
+impl SevenZip {
+    /// Mount `archive_path` (or the first volume of a multi-volume set, e.g.
+    /// `backup.7z.001`) read-only at `mount_point`, serving its contents over
+    /// FUSE until the mount is unmounted (`fusermount -u mount_point`) or the
+    /// process exits
+    ///
+    /// Blocks for the lifetime of the mount. Requires the `fuse` Cargo
+    /// feature; see this module's doc comment for the dependencies it needs.
+    pub fn mount(
+        &self,
+        archive_path: impl AsRef<Path>,
+        mount_point: impl AsRef<Path>,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let fs = ArchiveFs::new(archive_path.as_ref().to_path_buf(), password.map(String::from))?;
+        let options = vec![MountOption::RO, MountOption::FSName("sevenzip".to_string())];
+        fuser::mount2(fs, mount_point.as_ref(), &options)
+            .map_err(|e| Error::Io(format!("FUSE mount failed: {}", e)))
+    }
+}
@@ -0,0 +1,397 @@
+//! Self-describing segmented container with per-chunk CRC32 and a manifest
+//! footer
+//!
+//! The multi-volume path in [`crate::advanced::create_split_archive`] (and
+//! [`crate::archive::SevenZip::create_archive_streaming`]'s `split_size`)
+//! hands off to the native library's own `.001`/`.002` volume scheme, which
+//! is an opaque byte split: a missing, reordered, or corrupted volume is
+//! only discovered when extraction fails partway through, with no way to
+//! name which volume was the problem. [`SegmentWriter`] instead frames the
+//! payload as a sequence of numbered chunks, each prefixed with its index,
+//! length, and an IEEE CRC32, followed by a footer listing every chunk's
+//! offset/length/CRC32 plus the total count. [`SegmentReader`] reads that
+//! footer first, then walks the chunks in order, validating each CRC and
+//! that the sequence is complete and contiguous before handing back any
+//! data, naming the exact chunk that's missing or corrupt when it isn't.
+
+use crate::archive::crc32_ieee;
+use crate::error::{Error, Result};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// 4-byte marker at the very start of the footer, so [`SegmentReader::open`]
+/// can tell a truncated file from one whose footer was never written
+const FOOTER_MAGIC: [u8; 4] = *b"SEG1";
+
+/// One chunk's entry in the footer manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ChunkRecord {
+    /// Byte offset of this chunk's header (not its data) from the start of
+    /// the stream
+    offset: u64,
+    /// Length of the chunk's stored data in bytes (excludes the header)
+    length: u32,
+    /// IEEE CRC32 of the chunk's data
+    crc32: u32,
+}
+
+/// Writes a payload as a sequence of fixed-size (except the last) chunks,
+/// each with its own length and CRC32, and a manifest footer describing all
+/// of them
+///
+/// Buffers at most one chunk (`chunk_size` bytes) of unwritten data at a
+/// time, so the payload itself is never held fully in memory regardless of
+/// total size; only the (much smaller) per-chunk manifest accumulates for
+/// the footer.
+pub struct SegmentWriter<W: Write> {
+    writer: W,
+    chunk_size: usize,
+    pending: Vec<u8>,
+    next_chunk_number: u64,
+    manifest: Vec<ChunkRecord>,
+    position: u64,
+}
+
+impl<W: Write> SegmentWriter<W> {
+    /// Wrap `writer`, splitting written data into chunks of at most
+    /// `chunk_size` bytes each
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn new(writer: W, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk_size must be non-zero");
+        Self {
+            writer,
+            chunk_size,
+            pending: Vec::with_capacity(chunk_size),
+            next_chunk_number: 0,
+            manifest: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Number of complete chunks already flushed (an in-progress, not yet
+    /// full, chunk doesn't count until [`Self::finish`])
+    pub fn chunks_written(&self) -> u64 {
+        self.next_chunk_number
+    }
+
+    fn flush_chunk(&mut self, data: &[u8]) -> Result<()> {
+        let chunk_number = self.next_chunk_number;
+        let crc32 = crc32_ieee(data);
+        let header_offset = self.position;
+
+        self.writer.write_all(&chunk_number.to_le_bytes())?;
+        self.writer.write_all(&(data.len() as u32).to_le_bytes())?;
+        self.writer.write_all(&crc32.to_le_bytes())?;
+        self.writer.write_all(data)?;
+
+        self.manifest.push(ChunkRecord {
+            offset: header_offset,
+            length: data.len() as u32,
+            crc32,
+        });
+        self.position += CHUNK_HEADER_SIZE as u64 + data.len() as u64;
+        self.next_chunk_number += 1;
+        Ok(())
+    }
+
+    /// Flush any remaining buffered data as a final (possibly short) chunk,
+    /// then write the manifest footer, returning the wrapped writer
+    ///
+    /// Writing nothing at all still produces a valid, empty segmented
+    /// stream: a footer with zero chunks.
+    pub fn finish(mut self) -> Result<W> {
+        if !self.pending.is_empty() {
+            let data = std::mem::take(&mut self.pending);
+            self.flush_chunk(&data)?;
+        }
+
+        let footer_offset = self.position;
+        self.writer.write_all(&FOOTER_MAGIC)?;
+        self.writer
+            .write_all(&(self.manifest.len() as u64).to_le_bytes())?;
+        for record in &self.manifest {
+            self.writer.write_all(&record.offset.to_le_bytes())?;
+            self.writer.write_all(&record.length.to_le_bytes())?;
+            self.writer.write_all(&record.crc32.to_le_bytes())?;
+        }
+        self.writer.write_all(&footer_offset.to_le_bytes())?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> Write for SegmentWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // Accept only what fits in the current chunk, so `pending` never
+        // holds more than `chunk_size` bytes; `write_all` loops for the rest.
+        let take = buf.len().min(self.chunk_size - self.pending.len());
+        self.pending.extend_from_slice(&buf[..take]);
+        if self.pending.len() == self.chunk_size {
+            let data = std::mem::take(&mut self.pending);
+            let result = self.flush_chunk(&data);
+            self.pending = data;
+            self.pending.clear();
+            result.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+        }
+        Ok(take)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// `chunk_number` (u64) + `length` (u32) + `crc32` (u32), in front of every
+/// chunk's data
+const CHUNK_HEADER_SIZE: usize = 8 + 4 + 4;
+
+/// `offset` (u64) + `length` (u32) + `crc32` (u32), one footer manifest entry
+const CHUNK_RECORD_SIZE: usize = 8 + 4 + 4;
+
+/// Reads and validates a stream written by [`SegmentWriter`]
+///
+/// [`Self::open`] seeks to the footer first and reads the manifest without
+/// touching any chunk data; [`Self::read_all`] then walks the chunks in
+/// manifest order, recomputing each one's CRC32 and confirming the chunk
+/// numbers form an unbroken `0..n` sequence before returning the
+/// concatenated, verified payload.
+pub struct SegmentReader<R: Read + Seek> {
+    reader: R,
+    manifest: Vec<ChunkRecord>,
+}
+
+impl<R: Read + Seek> SegmentReader<R> {
+    /// Read and validate the footer, without yet reading or verifying any
+    /// chunk data
+    pub fn open(mut reader: R) -> Result<Self> {
+        let end = reader.seek(SeekFrom::End(0))?;
+        if end < 8 {
+            return Err(Error::InvalidArchive(
+                "segment stream too short to contain a footer".to_string(),
+            ));
+        }
+        reader.seek(SeekFrom::End(-8))?;
+        let footer_offset = read_u64(&mut reader)?;
+        if footer_offset.saturating_add(8) > end {
+            return Err(Error::InvalidArchive(
+                "segment footer offset points outside the stream".to_string(),
+            ));
+        }
+
+        reader.seek(SeekFrom::Start(footer_offset))?;
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != FOOTER_MAGIC {
+            return Err(Error::InvalidArchive(
+                "segment footer magic mismatch; stream is truncated or not a segmented container"
+                    .to_string(),
+            ));
+        }
+
+        let chunk_count = read_u64(&mut reader)?;
+
+        // Each manifest record is `CHUNK_RECORD_SIZE` bytes; bound
+        // `chunk_count` against how many of those can actually fit between
+        // here and the trailing footer-offset field before trusting it as a
+        // `Vec::with_capacity` argument - otherwise a corrupted or
+        // adversarial stream claiming e.g. `u64::MAX` chunks aborts the
+        // process with a capacity overflow rather than failing cleanly.
+        let bytes_remaining = end
+            .saturating_sub(8)
+            .saturating_sub(footer_offset.saturating_add(4).saturating_add(8));
+        let max_chunk_count = bytes_remaining / CHUNK_RECORD_SIZE as u64;
+        if chunk_count > max_chunk_count {
+            return Err(Error::InvalidArchive(format!(
+                "segment footer claims {} chunks, but only {} bytes remain ({} max)",
+                chunk_count, bytes_remaining, max_chunk_count
+            )));
+        }
+
+        let mut manifest = Vec::with_capacity(chunk_count as usize);
+        for _ in 0..chunk_count {
+            let offset = read_u64(&mut reader)?;
+            let length = read_u32(&mut reader)?;
+            let crc32 = read_u32(&mut reader)?;
+            manifest.push(ChunkRecord { offset, length, crc32 });
+        }
+
+        Ok(Self { reader, manifest })
+    }
+
+    /// Number of chunks the footer records
+    pub fn chunk_count(&self) -> usize {
+        self.manifest.len()
+    }
+
+    /// Serialize the manifest — chunk count plus each chunk's
+    /// offset/length/CRC32 — into a canonical byte form suitable for
+    /// hashing or signing
+    ///
+    /// Used by [`crate::signing::sign_archive`] so a signature can cover
+    /// (and [`crate::signing::verify_archive_signature`] can check) the
+    /// whole chunk sequence's integrity without reading any chunk's data.
+    pub fn manifest_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(8 + self.manifest.len() * 16);
+        buf.extend_from_slice(&(self.manifest.len() as u64).to_le_bytes());
+        for record in &self.manifest {
+            buf.extend_from_slice(&record.offset.to_le_bytes());
+            buf.extend_from_slice(&record.length.to_le_bytes());
+            buf.extend_from_slice(&record.crc32.to_le_bytes());
+        }
+        buf
+    }
+
+    /// Read every chunk in order, verifying its CRC32 and that the chunk
+    /// numbers form an unbroken `0..n` sequence, and return the
+    /// concatenated plaintext payload
+    ///
+    /// Fails with [`Error::InvalidArchive`] naming the specific chunk index
+    /// on the first missing, out-of-order, or CRC-mismatched chunk, rather
+    /// than silently concatenating whatever bytes happen to be present.
+    pub fn read_all(&mut self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        for (expected_index, record) in self.manifest.iter().enumerate() {
+            self.reader.seek(SeekFrom::Start(record.offset))?;
+
+            let missing = |_| {
+                Error::InvalidArchive(format!(
+                    "segment chunk {} is missing or truncated",
+                    expected_index
+                ))
+            };
+            let chunk_number = read_u64(&mut self.reader).map_err(missing)?;
+            let length = read_u32(&mut self.reader).map_err(missing)?;
+            let crc32 = read_u32(&mut self.reader).map_err(missing)?;
+
+            if chunk_number != expected_index as u64 {
+                return Err(Error::InvalidArchive(format!(
+                    "segment chunk {} has out-of-sequence chunk number {} (expected a contiguous 0..n sequence)",
+                    expected_index, chunk_number
+                )));
+            }
+            if length != record.length {
+                return Err(Error::InvalidArchive(format!(
+                    "segment chunk {} header length {} does not match footer length {}",
+                    expected_index, length, record.length
+                )));
+            }
+
+            let mut data = vec![0u8; length as usize];
+            self.reader.read_exact(&mut data).map_err(|_| {
+                Error::InvalidArchive(format!(
+                    "segment chunk {} is missing or truncated",
+                    expected_index
+                ))
+            })?;
+
+            let actual_crc32 = crc32_ieee(&data);
+            if actual_crc32 != crc32 || actual_crc32 != record.crc32 {
+                return Err(Error::InvalidArchive(format!(
+                    "segment chunk {} failed CRC32 verification (corrupt data)",
+                    expected_index
+                )));
+            }
+
+            out.extend_from_slice(&data);
+        }
+        Ok(out)
+    }
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_roundtrip_single_chunk() {
+        let payload = b"hello segmented world";
+        let mut writer = SegmentWriter::new(Vec::new(), 1024);
+        writer.write_all(payload).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = SegmentReader::open(Cursor::new(bytes)).unwrap();
+        assert_eq!(reader.chunk_count(), 1);
+        assert_eq!(reader.read_all().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_roundtrip_multiple_chunks() {
+        let payload: Vec<u8> = (0..10_000).map(|i| (i % 251) as u8).collect();
+        let mut writer = SegmentWriter::new(Vec::new(), 777);
+        for chunk in payload.chunks(133) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert!(writer.chunks_written() > 1);
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = SegmentReader::open(Cursor::new(bytes)).unwrap();
+        assert_eq!(reader.read_all().unwrap(), payload);
+    }
+
+    #[test]
+    fn test_empty_payload_roundtrip() {
+        let writer = SegmentWriter::new(Vec::new(), 64);
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = SegmentReader::open(Cursor::new(bytes)).unwrap();
+        assert_eq!(reader.chunk_count(), 0);
+        assert_eq!(reader.read_all().unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_detects_corrupted_chunk() {
+        let mut writer = SegmentWriter::new(Vec::new(), 16);
+        writer.write_all(b"0123456789abcdef0123456789abcdef").unwrap();
+        let mut bytes = writer.finish().unwrap();
+
+        // Flip a bit inside the first chunk's data, after its header.
+        bytes[CHUNK_HEADER_SIZE] ^= 0x01;
+
+        let mut reader = SegmentReader::open(Cursor::new(bytes)).unwrap();
+        match reader.read_all() {
+            Err(Error::InvalidArchive(msg)) => assert!(msg.contains("chunk 0")),
+            other => panic!("expected InvalidArchive naming chunk 0, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_open_rejects_truncated_stream() {
+        let err = SegmentReader::open(Cursor::new(vec![0u8; 4])).unwrap_err();
+        assert!(matches!(err, Error::InvalidArchive(_)));
+    }
+
+    #[test]
+    fn test_open_rejects_missing_footer_magic() {
+        let err = SegmentReader::open(Cursor::new(vec![0u8; 64])).unwrap_err();
+        assert!(matches!(err, Error::InvalidArchive(_)));
+    }
+
+    #[test]
+    fn test_open_rejects_chunk_count_exceeding_stream_length() {
+        let mut writer = SegmentWriter::new(Vec::new(), 16);
+        writer.write_all(b"0123456789abcdef").unwrap();
+        let mut bytes = writer.finish().unwrap();
+
+        // Overwrite the footer's chunk_count (immediately after the 4-byte
+        // magic) with a huge value that can't possibly fit in the stream.
+        let footer_offset = u64::from_le_bytes(bytes[bytes.len() - 8..].try_into().unwrap()) as usize;
+        bytes[footer_offset + 4..footer_offset + 12].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let err = SegmentReader::open(Cursor::new(bytes)).unwrap_err();
+        assert!(matches!(err, Error::InvalidArchive(_)));
+    }
+}
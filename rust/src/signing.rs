@@ -0,0 +1,296 @@
+//! Ed25519 detached signing and verification for [`crate::segment`] containers
+//!
+//! Provenance, not just confidentiality: [`sign_archive`] hashes the segment
+//! manifest footer — chunk count plus every chunk's offset/length/CRC32, via
+//! [`crate::segment::SegmentReader::manifest_bytes`] — together with every
+//! chunk's actual decoded content (read and CRC-verified the same way
+//! [`crate::segment::SegmentReader::read_all`] does) with SHA-256, and signs
+//! that digest with Ed25519, then appends the signature after the container.
+//! Hashing the manifest alone isn't enough: its per-chunk CRC32 is a
+//! forgeable checksum, not a cryptographic one, so content could be
+//! substituted for anything with a matching CRC32 without the signed digest
+//! changing. [`verify_archive_signature`] re-derives the same digest —
+//! reading and CRC-checking every chunk in the process — and checks it
+//! against a caller-supplied public key.
+
+use crate::error::{Error, Result};
+use crate::segment::SegmentReader;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{Cursor, Write};
+use std::path::Path;
+
+/// 4-byte marker in front of the signature block appended by [`sign_archive`]
+const SIGNATURE_MAGIC: [u8; 4] = *b"SIG1";
+
+/// `SIGNATURE_MAGIC` (4 bytes) + a 64-byte Ed25519 signature
+const SIGNATURE_BLOCK_LEN: usize = 4 + 64;
+
+/// Hash a container's manifest together with its actual chunk content
+///
+/// Reads and CRC-verifies every chunk via
+/// [`crate::segment::SegmentReader::read_all`], so this is no longer a
+/// metadata-only operation — it costs as much as a full extract.
+fn signing_digest<R: std::io::Read + std::io::Seek>(
+    reader: &mut SegmentReader<R>,
+) -> Result<[u8; 32]> {
+    let manifest_bytes = reader.manifest_bytes();
+    let content = reader.read_all()?;
+    let mut hasher = Sha256::new();
+    hasher.update(&manifest_bytes);
+    hasher.update(&content);
+    Ok(hasher.finalize().into())
+}
+
+/// Sign a [`crate::segment::SegmentWriter`]-produced file in place
+///
+/// Appends a small trailer — a magic marker and the 64-byte Ed25519
+/// signature over the SHA-256 digest of the container's manifest and every
+/// chunk's actual content — after the file's existing footer. A file that
+/// already carries a trailer no longer ends with the footer's offset field,
+/// so [`SegmentReader::open`] does not find the footer and signing it again
+/// returns an error; only a freshly written container can be signed.
+pub fn sign_archive(path: impl AsRef<Path>, signing_key: &SigningKey) -> Result<()> {
+    let path = path.as_ref();
+    let file = File::open(path)?;
+    let mut reader = SegmentReader::open(file)?;
+    let digest = signing_digest(&mut reader)?;
+    let signature = signing_key.sign(&digest);
+
+    let mut out = OpenOptions::new().append(true).open(path)?;
+    out.write_all(&SIGNATURE_MAGIC)?;
+    out.write_all(&signature.to_bytes())?;
+    Ok(())
+}
+
+/// Verify a file signed by [`sign_archive`] against `public_key`, returning
+/// whether the archive is authentic and untampered
+///
+/// Strips the trailing signature block, re-opens what's left as a
+/// [`crate::segment::SegmentReader`] to recompute the digest
+/// [`sign_archive`] signed — reading and CRC-checking every chunk's actual
+/// content along the way, so tampered content can't hide behind a
+/// recomputed CRC32 the way it could if the signature covered only the
+/// manifest — and checks the signature against it. Returns `Ok(false)` (not
+/// an error) for a well-formed but non-matching signature; only a malformed
+/// file, missing signature block, or unreadable/CRC-mismatched chunk is an
+/// `Err`.
+pub fn verify_archive_signature(
+    path: impl AsRef<Path>,
+    public_key: &VerifyingKey,
+) -> Result<bool> {
+    let mut bytes = std::fs::read(path.as_ref())?;
+    if bytes.len() < SIGNATURE_BLOCK_LEN {
+        return Err(Error::InvalidArchive(
+            "archive too short to contain a signature block".to_string(),
+        ));
+    }
+
+    let split_at = bytes.len() - SIGNATURE_BLOCK_LEN;
+    let sig_block = bytes.split_off(split_at);
+    if sig_block[..4] != SIGNATURE_MAGIC {
+        return Err(Error::InvalidArchive(
+            "signature magic mismatch; archive was not signed with sign_archive".to_string(),
+        ));
+    }
+    let signature_bytes: [u8; 64] = sig_block[4..68]
+        .try_into()
+        .expect("sig_block is exactly SIGNATURE_BLOCK_LEN bytes");
+    let signature = Signature::from_bytes(&signature_bytes);
+
+    let mut reader = SegmentReader::open(Cursor::new(bytes))?;
+    let digest = signing_digest(&mut reader)?;
+
+    Ok(public_key.verify(&digest, &signature).is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::SegmentWriter;
+    use ed25519_dalek::SigningKey;
+    use rand::rngs::OsRng;
+    use std::io::Write as _;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fn unique_temp_path() -> std::path::PathBuf {
+        let n = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("seven_zip_signing_{}_{}.seg", std::process::id(), n))
+    }
+
+    fn write_segmented_file(path: &std::path::Path, payload: &[u8]) {
+        let file = File::create(path).unwrap();
+        let mut writer = SegmentWriter::new(file, 64);
+        writer.write_all(payload).unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_sign_and_verify_roundtrip() {
+        let path = unique_temp_path();
+        write_segmented_file(&path, b"provenance matters for forensic evidence");
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        sign_archive(&path, &signing_key).unwrap();
+
+        let verified = verify_archive_signature(&path, &signing_key.verifying_key()).unwrap();
+        assert!(verified);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_public_key() {
+        let path = unique_temp_path();
+        write_segmented_file(&path, b"some archived bytes");
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        sign_archive(&path, &signing_key).unwrap();
+
+        let other_key = SigningKey::generate(&mut OsRng);
+        let verified = verify_archive_signature(&path, &other_key.verifying_key()).unwrap();
+        assert!(!verified);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_manifest() {
+        let path = unique_temp_path();
+        write_segmented_file(&path, b"tamper with the chunk manifest");
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        sign_archive(&path, &signing_key).unwrap();
+
+        // Flip a byte of the manifest record's crc32, which sits just before
+        // the footer's trailing 8-byte offset field and the signature trailer.
+        let mut bytes = std::fs::read(&path).unwrap();
+        let flip_at = bytes.len() - SIGNATURE_BLOCK_LEN - 8 - 1;
+        bytes[flip_at] ^= 0x01;
+        std::fs::write(&path, &bytes).unwrap();
+
+        // The manifest no longer matches the chunk it describes, so the file
+        // is rejected as malformed before the signature itself is checked.
+        let result = verify_archive_signature(&path, &signing_key.verifying_key());
+        assert!(matches!(result, Err(Error::InvalidArchive(_))));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    /// `chunk_number` (u64) + `length` (u32) + `crc32` (u32), mirroring
+    /// `segment::CHUNK_HEADER_SIZE` (private to that module)
+    const CHUNK_HEADER_SIZE: usize = 8 + 4 + 4;
+
+    /// Find 4 bytes to append to `prefix` so the combined buffer's CRC32
+    /// equals `target_crc`
+    ///
+    /// CRC32's per-byte update is linear over GF(2) for a fixed prefix, so
+    /// the effect of each of the appended suffix's 32 bits on the output
+    /// CRC can be measured independently and the resulting 32x32 binary
+    /// matrix inverted with Gaussian elimination — used below to build a
+    /// fixture that tampers a chunk's content while keeping its CRC32
+    /// identical to the original, the exact forgery [`sign_archive`]
+    /// hashing the manifest alone couldn't detect.
+    fn force_crc32_suffix(prefix: &[u8], target_crc: u32) -> [u8; 4] {
+        use crate::archive::crc32_ieee;
+
+        let eval = |suffix: [u8; 4]| crc32_ieee(&[prefix, &suffix].concat());
+        let baseline = eval([0, 0, 0, 0]);
+
+        let mut columns = [0u32; 32];
+        for (bit, column) in columns.iter_mut().enumerate() {
+            let mut suffix = [0u8; 4];
+            suffix[bit / 8] |= 1 << (bit % 8);
+            *column = eval(suffix) ^ baseline;
+        }
+
+        let target = target_crc ^ baseline;
+
+        // Gaussian elimination over GF(2): row `r` holds the coefficients
+        // (over the 32 suffix bits) of output bit `r`, plus that output
+        // bit's target value.
+        let mut rows: Vec<(u32, u32)> = (0..32)
+            .map(|r| {
+                let coeffs = (0..32).fold(0u32, |acc, bit| acc | (((columns[bit] >> r) & 1) << bit));
+                (coeffs, (target >> r) & 1)
+            })
+            .collect();
+
+        let mut pivot_row_for_col = [usize::MAX; 32];
+        let mut next_row = 0;
+        for col in 0..32 {
+            if let Some(pivot) = (next_row..32).find(|&r| (rows[r].0 >> col) & 1 == 1) {
+                rows.swap(next_row, pivot);
+                for r in 0..32 {
+                    if r != next_row && (rows[r].0 >> col) & 1 == 1 {
+                        rows[r].0 ^= rows[next_row].0;
+                        rows[r].1 ^= rows[next_row].1;
+                    }
+                }
+                pivot_row_for_col[col] = next_row;
+                next_row += 1;
+            }
+        }
+
+        let mut solution = 0u32;
+        for (col, &row) in pivot_row_for_col.iter().enumerate() {
+            if row != usize::MAX {
+                solution |= rows[row].1 << col;
+            }
+        }
+        solution.to_le_bytes()
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_content_with_preserved_crc32() {
+        use crate::archive::crc32_ieee;
+
+        let path = unique_temp_path();
+        let tampered_prefix = b"DIFFERENT CONTENT\0\0".to_vec();
+        let original_payload = {
+            let mut p = b"original archived data\0".to_vec();
+            assert_eq!(p.len(), tampered_prefix.len() + 4);
+            p.truncate(tampered_prefix.len() + 4);
+            p
+        };
+        write_segmented_file(&path, &original_payload);
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        sign_archive(&path, &signing_key).unwrap();
+
+        let original_crc = crc32_ieee(&original_payload);
+        let patch = force_crc32_suffix(&tampered_prefix, original_crc);
+        let mut forged_payload = tampered_prefix.clone();
+        forged_payload.extend_from_slice(&patch);
+        assert_eq!(crc32_ieee(&forged_payload), original_crc);
+        assert_ne!(forged_payload, original_payload);
+
+        // Overwrite only the chunk's data bytes — its header crc32 and the
+        // footer's manifest crc32 are left completely untouched, since the
+        // forged content was built to match the original CRC32 exactly.
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[CHUNK_HEADER_SIZE..CHUNK_HEADER_SIZE + forged_payload.len()]
+            .copy_from_slice(&forged_payload);
+        std::fs::write(&path, &bytes).unwrap();
+
+        let verified = verify_archive_signature(&path, &signing_key.verifying_key()).unwrap();
+        assert!(!verified, "signature must not validate tampered content even with a matching CRC32");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_truncated_file() {
+        let path = unique_temp_path();
+        std::fs::write(&path, b"too short").unwrap();
+
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let err = verify_archive_signature(&path, &signing_key.verifying_key()).unwrap_err();
+        assert!(matches!(err, Error::InvalidArchive(_)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+//! Minimal ustar reader/writer
+//!
+//! Just enough of the POSIX ustar format to support `.tar` / `.tar.xz` in
+//! [`crate::format`]'s extension-driven facade, without pulling in an
+//! external `tar` dependency. Only regular files are handled; directory
+//! entries are flattened into their file paths the way `ouch`'s tar layer does.
+
+use crate::error::{Error, Result};
+use std::io::Read;
+use std::path::Path;
+
+const BLOCK_SIZE: usize = 512;
+
+fn octal_field(value: u64, width: usize) -> Vec<u8> {
+    let mut s = format!("{:0width$o}", value, width = width - 1);
+    s.truncate(width - 1);
+    let mut field = s.into_bytes();
+    field.push(0);
+    field
+}
+
+fn checksum(header: &[u8; BLOCK_SIZE]) -> u32 {
+    header.iter().map(|&b| b as u32).sum()
+}
+
+fn write_entry(out: &mut Vec<u8>, name: &str, data: &[u8]) -> Result<()> {
+    if name.len() >= 100 {
+        return Err(Error::InvalidParameter(format!(
+            "tar entry name too long for ustar header: {}",
+            name
+        )));
+    }
+
+    let mut header = [0u8; BLOCK_SIZE];
+    header[0..name.len()].copy_from_slice(name.as_bytes());
+    header[100..107].copy_from_slice(&octal_field(0o644, 8)[..7]);
+    header[108..115].copy_from_slice(&octal_field(0, 8)[..7]); // uid
+    header[116..123].copy_from_slice(&octal_field(0, 8)[..7]); // gid
+    let size_field = octal_field(data.len() as u64, 12);
+    header[124..124 + size_field.len()].copy_from_slice(&size_field);
+    let mtime_field = octal_field(0, 12);
+    header[136..136 + mtime_field.len()].copy_from_slice(&mtime_field);
+    header[156] = b'0'; // regular file typeflag
+
+    // Checksum field is computed with itself blanked to spaces
+    header[148..156].copy_from_slice(b"        ");
+    let sum = checksum(&header);
+    let sum_field = format!("{:06o}\0 ", sum);
+    header[148..148 + sum_field.len()].copy_from_slice(sum_field.as_bytes());
+
+    out.extend_from_slice(&header);
+    out.extend_from_slice(data);
+    let padding = (BLOCK_SIZE - (data.len() % BLOCK_SIZE)) % BLOCK_SIZE;
+    out.extend(std::iter::repeat(0u8).take(padding));
+    Ok(())
+}
+
+/// Pack the given input paths into an in-memory ustar byte stream
+pub fn pack(input_paths: &[impl AsRef<Path>]) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+
+    for path in input_paths {
+        let path = path.as_ref();
+        if path.is_dir() {
+            for entry in walk_dir(path)? {
+                let relative = entry
+                    .strip_prefix(path.parent().unwrap_or(Path::new("")))
+                    .unwrap_or(&entry);
+                let name = relative.to_string_lossy().replace('\\', "/");
+                let data = std::fs::read(&entry)?;
+                write_entry(&mut out, &name, &data)?;
+            }
+        } else {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string());
+            let data = std::fs::read(path)?;
+            write_entry(&mut out, &name, &data)?;
+        }
+    }
+
+    // Two all-zero blocks mark the end of the archive
+    out.extend(std::iter::repeat(0u8).take(BLOCK_SIZE * 2));
+    Ok(out)
+}
+
+// Shared with `crate::zip_format`, which needs the same directory-to-file-list
+// flattening when packing a directory entry.
+pub(crate) fn walk_dir(dir: &Path) -> Result<Vec<std::path::PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(walk_dir(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn parse_octal(field: &[u8]) -> u64 {
+    let s = String::from_utf8_lossy(field);
+    u64::from_str_radix(s.trim_matches(|c: char| c == '\0' || c.is_whitespace()), 8).unwrap_or(0)
+}
+
+/// Unpack a ustar byte stream into `output_dir`, recreating any subdirectories
+pub fn unpack(mut reader: impl Read, output_dir: impl AsRef<Path>) -> Result<()> {
+    let output_dir = output_dir.as_ref();
+    std::fs::create_dir_all(output_dir)?;
+
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+
+    let mut offset = 0;
+    while offset + BLOCK_SIZE <= data.len() {
+        let header = &data[offset..offset + BLOCK_SIZE];
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+
+        let name_end = header[0..100].iter().position(|&b| b == 0).unwrap_or(100);
+        let name = String::from_utf8_lossy(&header[0..name_end]).to_string();
+        let size = parse_octal(&header[124..136]) as usize;
+
+        offset += BLOCK_SIZE;
+        let content = &data[offset..offset + size];
+
+        let out_path = output_dir.join(&name);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&out_path, content)?;
+
+        offset += size;
+        let padding = (BLOCK_SIZE - (size % BLOCK_SIZE)) % BLOCK_SIZE;
+        offset += padding;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_pack_unpack_roundtrip() {
+        let temp = std::env::temp_dir().join(format!("tar_format_test_{}", std::process::id()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let file_path = temp.join("hello.txt");
+        std::fs::write(&file_path, b"Hello, tar!").unwrap();
+
+        let packed = pack(&[&file_path]).unwrap();
+
+        let out_dir = temp.join("out");
+        unpack(Cursor::new(packed), &out_dir).unwrap();
+
+        let roundtripped = std::fs::read(out_dir.join("hello.txt")).unwrap();
+        assert_eq!(roundtripped, b"Hello, tar!");
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+}
@@ -0,0 +1,637 @@
+//! Minimal ZIP writer with WinZip AE-2 strong encryption
+//!
+//! The 7z SDK this crate wraps has no ZIP codec (see `examples/test_formats.rs`),
+//! so interoperating with tools that only open ZIP (not 7z) needs a writer of
+//! our own. Entries are stored uncompressed (method 0, "Store") — there is no
+//! Deflate implementation here, only the container format and the WinZip AE-2
+//! encryption layer; use `.7z` via [`crate::archive::SevenZip::create_archive`]
+//! when compression matters.
+//!
+//! Implements the WinZip AE-2 scheme: PBKDF2-HMAC-SHA1 (1000 iterations) over
+//! the password derives the AES key, an HMAC-SHA1 authentication key of the
+//! same length, and a 2-byte password verification value; file data is then
+//! encrypted with AES in CTR mode using a little-endian block counter that
+//! starts at 1, and authenticated with the first 10 bytes of an HMAC-SHA1 over
+//! the ciphertext. AE-2 (unlike AE-1) omits the plaintext CRC-32 from the
+//! local/central headers, relying on the HMAC for integrity instead.
+//!
+//! [`extract_zip_archive`] also reads back entries encrypted with the older,
+//! weaker PKWARE stream cipher ("ZipCrypto") for compatibility with archives
+//! produced before AE-2 existed; this crate never writes that format.
+
+use crate::archive::CRC32_TABLE;
+use crate::error::{Error, Result};
+use crate::tar_format;
+use aes::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use sha1::Sha1;
+use std::path::Path;
+use subtle::ConstantTimeEq;
+
+type HmacSha1 = Hmac<Sha1>;
+
+const PBKDF2_ITERATIONS: u32 = 1000;
+const PWD_VERIFY_LEN: usize = 2;
+const AUTH_CODE_LEN: usize = 10;
+/// WinZip AE extra field header id
+const AE_EXTRA_ID: u16 = 0x9901;
+/// Compression method recorded for any AE-x entry, with the real codec
+/// (here always 0 = Store) carried in the AE extra field instead
+const METHOD_AE_X: u16 = 99;
+
+/// AES key strength for WinZip AE-2 encryption
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesStrength {
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+impl Default for AesStrength {
+    fn default() -> Self {
+        AesStrength::Aes256
+    }
+}
+
+impl AesStrength {
+    fn key_len(self) -> usize {
+        match self {
+            AesStrength::Aes128 => 16,
+            AesStrength::Aes192 => 24,
+            AesStrength::Aes256 => 32,
+        }
+    }
+
+    fn salt_len(self) -> usize {
+        match self {
+            AesStrength::Aes128 => 8,
+            AesStrength::Aes192 => 12,
+            AesStrength::Aes256 => 16,
+        }
+    }
+
+    /// Vendor strength byte recorded in the AE extra field (1/2/3 = 128/192/256)
+    fn mode_byte(self) -> u8 {
+        match self {
+            AesStrength::Aes128 => 1,
+            AesStrength::Aes192 => 2,
+            AesStrength::Aes256 => 3,
+        }
+    }
+
+    fn from_mode_byte(b: u8) -> Result<Self> {
+        match b {
+            1 => Ok(AesStrength::Aes128),
+            2 => Ok(AesStrength::Aes192),
+            3 => Ok(AesStrength::Aes256),
+            other => Err(Error::InvalidArchive(format!(
+                "unrecognized AE-x strength byte: {}",
+                other
+            ))),
+        }
+    }
+}
+
+enum AesCipher {
+    Aes128(aes::Aes128),
+    Aes192(aes::Aes192),
+    Aes256(aes::Aes256),
+}
+
+impl AesCipher {
+    fn new(strength: AesStrength, key: &[u8]) -> Self {
+        match strength {
+            AesStrength::Aes128 => AesCipher::Aes128(aes::Aes128::new(GenericArray::from_slice(key))),
+            AesStrength::Aes192 => AesCipher::Aes192(aes::Aes192::new(GenericArray::from_slice(key))),
+            AesStrength::Aes256 => AesCipher::Aes256(aes::Aes256::new(GenericArray::from_slice(key))),
+        }
+    }
+
+    fn encrypt_block(&self, block: &mut GenericArray<u8, aes::cipher::consts::U16>) {
+        match self {
+            AesCipher::Aes128(c) => c.encrypt_block(block),
+            AesCipher::Aes192(c) => c.encrypt_block(block),
+            AesCipher::Aes256(c) => c.encrypt_block(block),
+        }
+    }
+}
+
+struct DerivedKeys {
+    aes_key: Vec<u8>,
+    hmac_key: Vec<u8>,
+    verify: [u8; PWD_VERIFY_LEN],
+}
+
+fn derive_keys(password: &str, salt: &[u8], strength: AesStrength) -> DerivedKeys {
+    let key_len = strength.key_len();
+    let mut okm = vec![0u8; key_len * 2 + PWD_VERIFY_LEN];
+    pbkdf2_hmac::<Sha1>(password.as_bytes(), salt, PBKDF2_ITERATIONS, &mut okm);
+
+    let aes_key = okm[..key_len].to_vec();
+    let hmac_key = okm[key_len..key_len * 2].to_vec();
+    let mut verify = [0u8; PWD_VERIFY_LEN];
+    verify.copy_from_slice(&okm[key_len * 2..]);
+
+    DerivedKeys { aes_key, hmac_key, verify }
+}
+
+/// AES-CTR over `data` in place, using the WinZip AE little-endian block
+/// counter that starts at 1 (not 0, and not the big-endian counter used by
+/// [`crate::encryption_native`]'s 7z-style streaming mode).
+fn ctr_transform_le(cipher: &AesCipher, data: &mut [u8]) {
+    let mut counter: u128 = 1;
+    let mut offset = 0;
+    while offset < data.len() {
+        let block_len = (data.len() - offset).min(16);
+        let mut counter_block = GenericArray::clone_from_slice(&counter.to_le_bytes());
+        cipher.encrypt_block(&mut counter_block);
+        for i in 0..block_len {
+            data[offset + i] ^= counter_block[i];
+        }
+        counter = counter.wrapping_add(1);
+        offset += block_len;
+    }
+}
+
+fn auth_code(hmac_key: &[u8], ciphertext: &[u8]) -> [u8; AUTH_CODE_LEN] {
+    let mut mac = HmacSha1::new_from_slice(hmac_key).expect("HMAC-SHA1 accepts any key length");
+    mac.update(ciphertext);
+    let full = mac.finalize().into_bytes();
+    let mut out = [0u8; AUTH_CODE_LEN];
+    out.copy_from_slice(&full[..AUTH_CODE_LEN]);
+    out
+}
+
+/// Classic PKWARE stream cipher ("ZipCrypto"), kept for read compatibility
+/// with archives from older 7-Zip/WinZip releases that predate AES-2.
+/// [`create_zip_archive`] never produces entries encrypted this way —
+/// [`extract_zip_archive`] only needs to decode them.
+struct ZipCryptoKeys {
+    key0: u32,
+    key1: u32,
+    key2: u32,
+}
+
+fn crc32_update(crc: u32, byte: u8) -> u32 {
+    CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+}
+
+impl ZipCryptoKeys {
+    fn new(password: &str) -> Self {
+        let mut keys = ZipCryptoKeys { key0: 0x1234_5678, key1: 0x2345_6789, key2: 0x3456_7890 };
+        for &byte in password.as_bytes() {
+            keys.update(byte);
+        }
+        keys
+    }
+
+    fn update(&mut self, plaintext_byte: u8) {
+        self.key0 = crc32_update(self.key0, plaintext_byte);
+        self.key1 = self.key1.wrapping_add(self.key0 & 0xff);
+        self.key1 = self.key1.wrapping_mul(134_775_813).wrapping_add(1);
+        self.key2 = crc32_update(self.key2, (self.key1 >> 24) as u8);
+    }
+
+    fn decrypt_byte(&self) -> u8 {
+        let temp = (self.key2 | 2) as u16;
+        (temp.wrapping_mul(temp ^ 1) >> 8) as u8
+    }
+
+    /// Decrypt `data` in place, updating the keystream with each recovered
+    /// plaintext byte as it goes
+    fn decrypt(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            let plain = *byte ^ self.decrypt_byte();
+            self.update(plain);
+            *byte = plain;
+        }
+    }
+}
+
+struct PlainEntry {
+    name: String,
+    data: Vec<u8>,
+}
+
+fn collect_entries(input_paths: &[impl AsRef<Path>]) -> Result<Vec<PlainEntry>> {
+    let mut entries = Vec::new();
+    for path in input_paths {
+        let path = path.as_ref();
+        if path.is_dir() {
+            for file in tar_format::walk_dir(path)? {
+                let relative = file
+                    .strip_prefix(path.parent().unwrap_or(Path::new("")))
+                    .unwrap_or(&file);
+                let name = relative.to_string_lossy().replace('\\', "/");
+                let data = std::fs::read(&file)?;
+                entries.push(PlainEntry { name, data });
+            }
+        } else {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string());
+            let data = std::fs::read(path)?;
+            entries.push(PlainEntry { name, data });
+        }
+    }
+    Ok(entries)
+}
+
+fn ae_extra_field(strength: AesStrength) -> Vec<u8> {
+    let mut extra = Vec::with_capacity(11);
+    extra.extend_from_slice(&AE_EXTRA_ID.to_le_bytes());
+    extra.extend_from_slice(&7u16.to_le_bytes()); // data size
+    extra.extend_from_slice(&2u16.to_le_bytes()); // AE-2
+    extra.extend_from_slice(b"AE"); // vendor id
+    extra.push(strength.mode_byte());
+    extra.extend_from_slice(&0u16.to_le_bytes()); // actual compression method: Store
+    extra
+}
+
+/// Pack `input_paths` into a WinZip AE-2 encrypted ZIP archive at `archive_path`
+///
+/// Directories are flattened the same way [`crate::tar_format::pack`] does.
+/// Entries are stored uncompressed; only the encryption layer is applied.
+pub fn create_zip_archive(
+    archive_path: impl AsRef<Path>,
+    input_paths: &[impl AsRef<Path>],
+    password: &str,
+    strength: AesStrength,
+) -> Result<()> {
+    let entries = collect_entries(input_paths)?;
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+
+    for entry in &entries {
+        let salt_len = strength.salt_len();
+        let mut salt = vec![0u8; salt_len];
+        getrandom(&mut salt)?;
+        let keys = derive_keys(password, &salt, strength);
+
+        let mut ciphertext = entry.data.clone();
+        let cipher = AesCipher::new(strength, &keys.aes_key);
+        ctr_transform_le(&cipher, &mut ciphertext);
+        let tag = auth_code(&keys.hmac_key, &ciphertext);
+
+        let mut payload = Vec::with_capacity(salt_len + PWD_VERIFY_LEN + ciphertext.len() + AUTH_CODE_LEN);
+        payload.extend_from_slice(&salt);
+        payload.extend_from_slice(&keys.verify);
+        payload.extend_from_slice(&ciphertext);
+        payload.extend_from_slice(&tag);
+
+        let extra = ae_extra_field(strength);
+        let name_bytes = entry.name.as_bytes();
+        let local_header_offset = out.len() as u32;
+
+        out.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        out.extend_from_slice(&51u16.to_le_bytes()); // version needed: 5.1 (AE-x)
+        out.extend_from_slice(&1u16.to_le_bytes()); // general purpose flag: bit 0 = encrypted
+        out.extend_from_slice(&METHOD_AE_X.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // mod time
+        out.extend_from_slice(&0u16.to_le_bytes()); // mod date
+        out.extend_from_slice(&0u32.to_le_bytes()); // crc-32: omitted under AE-2
+        out.extend_from_slice(&(payload.len() as u32).to_le_bytes()); // compressed size
+        out.extend_from_slice(&(entry.data.len() as u32).to_le_bytes()); // uncompressed size
+        out.extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+        out.extend_from_slice(name_bytes);
+        out.extend_from_slice(&extra);
+        out.extend_from_slice(&payload);
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&51u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&51u16.to_le_bytes()); // version needed
+        central.extend_from_slice(&1u16.to_le_bytes()); // flag
+        central.extend_from_slice(&METHOD_AE_X.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&0u32.to_le_bytes());
+        central.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        central.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+        central.extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+        central.extend_from_slice(&(extra.len() as u16).to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        central.extend_from_slice(&0u16.to_le_bytes()); // disk number start
+        central.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central.extend_from_slice(&local_header_offset.to_le_bytes());
+        central.extend_from_slice(name_bytes);
+        central.extend_from_slice(&extra);
+    }
+
+    let central_offset = out.len() as u32;
+    out.extend_from_slice(&central);
+
+    out.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // disk number
+    out.extend_from_slice(&0u16.to_le_bytes()); // disk with central dir
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+
+    std::fs::write(archive_path, out)?;
+    Ok(())
+}
+
+/// Extract a ZIP archive written by [`create_zip_archive`] into `output_dir`
+///
+/// Verifies the AE-2 password check value and HMAC-SHA1 authentication tag
+/// before decrypting each entry, returning [`Error::WrongPassword`] if the
+/// check value does not match and [`Error::DecryptionError`] if the tag does
+/// not, as soon as either check fails, rather than writing unverified
+/// plaintext.
+pub fn extract_zip_archive(
+    archive_path: impl AsRef<Path>,
+    output_dir: impl AsRef<Path>,
+    password: &str,
+) -> Result<()> {
+    let data = std::fs::read(archive_path)?;
+    let output_dir = output_dir.as_ref();
+    std::fs::create_dir_all(output_dir)?;
+
+    /// Fixed portion of a local file header, up to and including the
+    /// extra-field length, before the variable-length name/extra/data
+    const LOCAL_HEADER_LEN: usize = 30;
+
+    let mut offset = 0usize;
+    while offset + 4 <= data.len() && data[offset..offset + 4] == 0x0403_4b50u32.to_le_bytes() {
+        if offset + LOCAL_HEADER_LEN > data.len() {
+            return Err(Error::InvalidArchive(
+                "truncated ZIP local file header".to_string(),
+            ));
+        }
+        let header = &data[offset..];
+        let flag = u16::from_le_bytes([header[6], header[7]]);
+        let method = u16::from_le_bytes([header[8], header[9]]);
+        let crc32 = u32::from_le_bytes([header[14], header[15], header[16], header[17]]);
+        let compressed_size = u32::from_le_bytes([header[18], header[19], header[20], header[21]]) as usize;
+        let name_len = u16::from_le_bytes([header[26], header[27]]) as usize;
+        let extra_len = u16::from_le_bytes([header[28], header[29]]) as usize;
+
+        let name_start = offset + LOCAL_HEADER_LEN;
+        let extra_start = name_start + name_len;
+        let payload_start = extra_start + extra_len;
+        let payload_end = payload_start + compressed_size;
+        if payload_end > data.len() {
+            return Err(Error::InvalidArchive(
+                "ZIP entry's name/extra/data fields run past the end of the archive".to_string(),
+            ));
+        }
+
+        let name = String::from_utf8_lossy(&data[name_start..extra_start]).to_string();
+        let extra = &data[extra_start..payload_start];
+        let payload = &data[payload_start..payload_end];
+
+        if flag & 1 == 0 {
+            return Err(Error::NotImplemented(format!(
+                "only encrypted ZIP entries are supported (entry '{}')",
+                name
+            )));
+        }
+
+        let plaintext = if method == METHOD_AE_X {
+            decrypt_ae2_entry(&name, extra, payload, password)?
+        } else if method == 0 {
+            decrypt_zipcrypto_entry(&name, crc32, payload, password)?
+        } else {
+            return Err(Error::NotImplemented(format!(
+                "legacy ZipCrypto entries must use Store (got method {} on entry '{}')",
+                method, name
+            )));
+        };
+
+        let out_path = output_dir.join(&name);
+        if let Some(parent) = out_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&out_path, &plaintext)?;
+
+        offset = payload_end;
+    }
+
+    Ok(())
+}
+
+fn decrypt_ae2_entry(name: &str, extra: &[u8], payload: &[u8], password: &str) -> Result<Vec<u8>> {
+    let strength = parse_ae_strength(extra)?;
+
+    let salt_len = strength.salt_len();
+    let header_len = salt_len + PWD_VERIFY_LEN + AUTH_CODE_LEN;
+    if payload.len() < header_len {
+        return Err(Error::InvalidArchive(format!(
+            "truncated AE-2 payload on entry '{}'",
+            name
+        )));
+    }
+
+    let salt = &payload[..salt_len];
+    let stored_verify = &payload[salt_len..salt_len + PWD_VERIFY_LEN];
+    let ciphertext_end = payload.len() - AUTH_CODE_LEN;
+    let mut ciphertext = payload[salt_len + PWD_VERIFY_LEN..ciphertext_end].to_vec();
+    let stored_tag = &payload[ciphertext_end..];
+
+    let keys = derive_keys(password, salt, strength);
+    if !bool::from(keys.verify.ct_eq(stored_verify)) {
+        return Err(Error::WrongPassword(format!("incorrect password for entry '{}'", name)));
+    }
+    let expected_tag = auth_code(&keys.hmac_key, &ciphertext);
+    if !bool::from(expected_tag.ct_eq(stored_tag)) {
+        return Err(Error::DecryptionError(format!(
+            "authentication tag mismatch for entry '{}'",
+            name
+        )));
+    }
+
+    let cipher = AesCipher::new(strength, &keys.aes_key);
+    ctr_transform_le(&cipher, &mut ciphertext);
+    Ok(ciphertext)
+}
+
+/// Decrypt an entry encrypted with the classic PKWARE stream cipher
+/// ("ZipCrypto"), as produced by older 7-Zip/WinZip releases that predate
+/// AES. Only `Store`d (uncompressed) entries are handled, matching the only
+/// codec this module's writer ever produces.
+fn decrypt_zipcrypto_entry(name: &str, crc32: u32, payload: &[u8], password: &str) -> Result<Vec<u8>> {
+    const HEADER_LEN: usize = 12;
+    if payload.len() < HEADER_LEN {
+        return Err(Error::InvalidArchive(format!(
+            "truncated ZipCrypto header on entry '{}'",
+            name
+        )));
+    }
+
+    let mut keys = ZipCryptoKeys::new(password);
+    let mut header = payload[..HEADER_LEN].to_vec();
+    keys.decrypt(&mut header);
+    // The last decrypted header byte is the password check value: the high
+    // byte of the entry's CRC-32 (or, in archives predating CRC-first
+    // headers, the high byte of the mod time -- not handled here).
+    if header[HEADER_LEN - 1] != (crc32 >> 24) as u8 {
+        return Err(Error::WrongPassword(format!("incorrect password for entry '{}'", name)));
+    }
+
+    let mut plaintext = payload[HEADER_LEN..].to_vec();
+    keys.decrypt(&mut plaintext);
+    Ok(plaintext)
+}
+
+fn parse_ae_strength(extra: &[u8]) -> Result<AesStrength> {
+    let mut i = 0;
+    while i + 4 <= extra.len() {
+        let id = u16::from_le_bytes([extra[i], extra[i + 1]]);
+        let size = u16::from_le_bytes([extra[i + 2], extra[i + 3]]) as usize;
+        if id == AE_EXTRA_ID && i + 4 + size <= extra.len() && size >= 5 {
+            let field = &extra[i + 4..i + 4 + size];
+            return AesStrength::from_mode_byte(field[4]);
+        }
+        i += 4 + size;
+    }
+    Err(Error::InvalidArchive(
+        "missing AE-x extra field on encrypted entry".to_string(),
+    ))
+}
+
+fn getrandom(buf: &mut [u8]) -> Result<()> {
+    use rand::RngCore;
+    rand::thread_rng().fill_bytes(buf);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test-only mirror of the classic PKWARE cipher's encrypt direction
+    /// (real archives are written by other tools; this module never emits
+    /// ZipCrypto itself), used to build fixtures for `decrypt_zipcrypto_entry`.
+    fn encrypt_zipcrypto(password: &str, check_byte: u8, plaintext: &[u8]) -> Vec<u8> {
+        let mut keys = ZipCryptoKeys::new(password);
+        let mut out = Vec::with_capacity(12 + plaintext.len());
+        let header_plain = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, check_byte];
+        for &p in header_plain.iter().chain(plaintext.iter()) {
+            let c = p ^ keys.decrypt_byte();
+            keys.update(p);
+            out.push(c);
+        }
+        out
+    }
+
+    #[test]
+    fn test_zipcrypto_roundtrip() {
+        let crc = 0xDEAD_BEEFu32;
+        let plaintext = b"legacy PKWARE stream cipher".to_vec();
+        let payload = encrypt_zipcrypto("hunter2", (crc >> 24) as u8, &plaintext);
+
+        let decrypted = decrypt_zipcrypto_entry("legacy.txt", crc, &payload, "hunter2").unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_zipcrypto_wrong_password_rejected() {
+        let crc = 0xDEAD_BEEFu32;
+        let payload = encrypt_zipcrypto("hunter2", (crc >> 24) as u8, b"secret");
+
+        let err = decrypt_zipcrypto_entry("legacy.txt", crc, &payload, "wrong").unwrap_err();
+        assert!(matches!(err, Error::WrongPassword(_)));
+    }
+
+    #[test]
+    fn test_zip_roundtrip() {
+        let temp = std::env::temp_dir().join(format!("zip_format_test_{}", std::process::id()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let file_path = temp.join("secret.txt");
+        std::fs::write(&file_path, b"Hello, WinZip AE-2!").unwrap();
+
+        let archive_path = temp.join("out.zip");
+        create_zip_archive(&archive_path, &[&file_path], "hunter2", AesStrength::Aes256).unwrap();
+
+        let out_dir = temp.join("out");
+        extract_zip_archive(&archive_path, &out_dir, "hunter2").unwrap();
+
+        let roundtripped = std::fs::read(out_dir.join("secret.txt")).unwrap();
+        assert_eq!(roundtripped, b"Hello, WinZip AE-2!");
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn test_zip_wrong_password_rejected() {
+        let temp = std::env::temp_dir().join(format!("zip_format_wrongpw_{}", std::process::id()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let file_path = temp.join("secret.txt");
+        std::fs::write(&file_path, b"top secret").unwrap();
+
+        let archive_path = temp.join("out.zip");
+        create_zip_archive(&archive_path, &[&file_path], "correct horse", AesStrength::Aes128).unwrap();
+
+        let err = extract_zip_archive(&archive_path, temp.join("out"), "wrong password").unwrap_err();
+        assert!(matches!(err, Error::WrongPassword(_)));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn test_extract_zip_archive_rejects_truncated_local_header() {
+        let temp = std::env::temp_dir().join(format!("zip_format_trunc_header_{}", std::process::id()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let file_path = temp.join("secret.txt");
+        std::fs::write(&file_path, b"top secret").unwrap();
+
+        let archive_path = temp.join("out.zip");
+        create_zip_archive(&archive_path, &[&file_path], "hunter2", AesStrength::Aes256).unwrap();
+        let mut bytes = std::fs::read(&archive_path).unwrap();
+        // Truncate partway through the fixed local file header.
+        bytes.truncate(20);
+        std::fs::write(&archive_path, &bytes).unwrap();
+
+        let err = extract_zip_archive(&archive_path, temp.join("out"), "hunter2").unwrap_err();
+        assert!(matches!(err, Error::InvalidArchive(_)));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn test_extract_zip_archive_rejects_truncated_payload() {
+        let temp = std::env::temp_dir().join(format!("zip_format_trunc_payload_{}", std::process::id()));
+        std::fs::create_dir_all(&temp).unwrap();
+        let file_path = temp.join("secret.txt");
+        std::fs::write(&file_path, b"top secret").unwrap();
+
+        let archive_path = temp.join("out.zip");
+        create_zip_archive(&archive_path, &[&file_path], "hunter2", AesStrength::Aes256).unwrap();
+        let mut bytes = std::fs::read(&archive_path).unwrap();
+        // Cut off the central directory and end record plus the last 8 bytes
+        // of the entry's data, leaving the header intact but the declared
+        // payload length impossible to satisfy.
+        let eocd = bytes.len() - 22;
+        let central_offset = u32::from_le_bytes(bytes[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+        bytes.truncate(central_offset - 8);
+        std::fs::write(&archive_path, &bytes).unwrap();
+
+        let err = extract_zip_archive(&archive_path, temp.join("out"), "hunter2").unwrap_err();
+        assert!(matches!(err, Error::InvalidArchive(_)));
+
+        let _ = std::fs::remove_dir_all(&temp);
+    }
+
+    #[test]
+    fn test_decrypt_ae2_entry_rejects_short_payload() {
+        let extra = {
+            let mut e = vec![0u8; 11];
+            e[0..2].copy_from_slice(&AE_EXTRA_ID.to_le_bytes());
+            e[2..4].copy_from_slice(&7u16.to_le_bytes());
+            e[4..6].copy_from_slice(&2u16.to_le_bytes());
+            e[6..8].copy_from_slice(b"AE");
+            e[8] = AesStrength::Aes256.mode_byte();
+            e[9..11].copy_from_slice(&0u16.to_le_bytes());
+            e
+        };
+
+        let err = decrypt_ae2_entry("short.txt", &extra, b"too short", "hunter2").unwrap_err();
+        assert!(matches!(err, Error::InvalidArchive(_)));
+    }
+}